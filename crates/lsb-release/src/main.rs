@@ -0,0 +1,220 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! `lsb_release` compatible command backed by os-info
+//!
+//! Supports the options scripts rely on: `-a`, `-i`, `-d`, `-r`, `-c` and
+//! `-s`, including combined forms such as `-sc`. The data comes from the
+//! resolved os-info document rather than `/etc/lsb-release`.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use os_info::{Loader, LsbRelease};
+
+const USAGE: &str = "\
+Usage: lsb_release [options]
+
+Options:
+  -h, --help         show this help message and exit
+  -v, --version      show LSB modules this system supports
+  -i, --id           show distributor ID
+  -d, --description  show description of this distribution
+  -r, --release      show release number of this distribution
+  -c, --codename     show code name of this distribution
+  -a, --all          show all of the above information
+  -s, --short        show requested information in short format
+      --root DIR     read os-info below DIR instead of /";
+
+const NO_MODULES: &str = "No LSB modules are available.";
+
+/// Fields requested on the command line
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Options {
+    id: bool,
+    description: bool,
+    release: bool,
+    codename: bool,
+    short: bool,
+    version: bool,
+    root: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Help,
+    Show(Options),
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let flags = match arg.as_str() {
+            "--help" => return Ok(Command::Help),
+            "--version" => "v",
+            "--id" => "i",
+            "--description" => "d",
+            "--release" => "r",
+            "--codename" => "c",
+            "--all" => "a",
+            "--short" => "s",
+            "--root" => {
+                let dir = args.next().ok_or("option --root requires an argument")?;
+                options.root = Some(dir.into());
+                continue;
+            }
+            _ => {
+                if let Some(dir) = arg.strip_prefix("--root=") {
+                    options.root = Some(dir.into());
+                    continue;
+                }
+                match arg.strip_prefix('-') {
+                    Some(flags) if !flags.is_empty() && !flags.starts_with('-') => flags,
+                    _ => return Err(format!("unrecognized argument `{arg}`")),
+                }
+            }
+        };
+
+        for flag in flags.chars() {
+            match flag {
+                'h' => return Ok(Command::Help),
+                'v' => options.version = true,
+                'i' => options.id = true,
+                'd' => options.description = true,
+                'r' => options.release = true,
+                'c' => options.codename = true,
+                's' => options.short = true,
+                'a' => {
+                    options.version = true;
+                    options.id = true;
+                    options.description = true;
+                    options.release = true;
+                    options.codename = true;
+                }
+                _ => return Err(format!("no such option: -{flag}")),
+            }
+        }
+    }
+
+    Ok(Command::Show(options))
+}
+
+/// Render the requested fields in `lsb_release` layout
+fn render(release: &LsbRelease, options: &Options) -> String {
+    let codename = release.distrib_codename.as_deref().unwrap_or("n/a");
+    let fields = [
+        (options.id, "Distributor ID", release.distrib_id.as_str()),
+        (
+            options.description,
+            "Description",
+            release.distrib_description.as_str(),
+        ),
+        (options.release, "Release", release.distrib_release.as_str()),
+        (options.codename, "Codename", codename),
+    ];
+
+    let mut output = String::new();
+    for (_, label, value) in fields.into_iter().filter(|(requested, ..)| *requested) {
+        if options.short {
+            output.push_str(value);
+        } else {
+            output.push_str(&format!("{label}:\t{value}"));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Show(options)) => options,
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("lsb_release: {err}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let requested = options.id || options.description || options.release || options.codename;
+    if options.version || !requested {
+        eprintln!("{NO_MODULES}");
+    }
+    if !requested {
+        return ExitCode::SUCCESS;
+    }
+
+    let loader = match &options.root {
+        Some(root) => Loader::with_root(root),
+        None => Loader::new(),
+    };
+    let info = match loader.load() {
+        Ok(loaded) => loaded.info,
+        Err(err) => {
+            eprintln!("lsb_release: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    print!("{}", render(&LsbRelease::from(&info), &options));
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|a| a.to_string()))
+    }
+
+    fn release() -> LsbRelease {
+        LsbRelease {
+            distrib_id: "AerynOS".to_string(),
+            distrib_release: "0.25.1".to_string(),
+            distrib_codename: None,
+            distrib_description: "AerynOS 0.25.1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse(&["-sc", "--release", "--root", "/mnt"]),
+            Ok(Command::Show(Options {
+                codename: true,
+                release: true,
+                short: true,
+                root: Some("/mnt".into()),
+                ..Default::default()
+            }))
+        );
+        assert_eq!(parse(&["-ih"]), Ok(Command::Help));
+        assert_eq!(parse(&["-x"]), Err("no such option: -x".to_string()));
+        assert_eq!(
+            parse(&["release"]),
+            Err("unrecognized argument `release`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let Ok(Command::Show(all)) = parse(&["-a"]) else {
+            panic!("-a should parse");
+        };
+        assert_eq!(
+            render(&release(), &all),
+            "Distributor ID:\tAerynOS\nDescription:\tAerynOS 0.25.1\nRelease:\t0.25.1\nCodename:\tn/a\n"
+        );
+
+        let Ok(Command::Show(short)) = parse(&["-s", "-i", "-r"]) else {
+            panic!("-s -i -r should parse");
+        };
+        assert_eq!(render(&release(), &short), "AerynOS\n0.25.1\n");
+    }
+}
@@ -0,0 +1,101 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Generates `os-info.h` from the exported functions and `os-info.pc` from
+//! the crate version
+//!
+//! Both are written to `OUT_DIR` only, installing them is left to packaging.
+//! The pkg-config paths default to `/usr/local` and are overridden with the
+//! `PREFIX`, `LIBDIR` and `INCLUDEDIR` environment variables.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cbindgen::{Builder, Config, Language, Style};
+
+const HEADER: &str = "os-info.h";
+const PKG_CONFIG: &str = "os-info.pc";
+
+/// Comment placed at the top of the header
+const PREAMBLE: &str = "\
+/*
+ * SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+ *
+ * SPDX-License-Identifier: MPL-2.0
+ */
+
+/*
+ * C interface to os-info
+ *
+ * Documents are opaque handles. Strings returned by the library belong to the
+ * caller and are released with os_info_string_free(). Functions that can fail
+ * return NULL and, when `error` is not NULL, store an error that is released
+ * with os_info_error_free().
+ *
+ * Fields are looked up by JSON pointer (\"/metadata/identity/name\") or dotted
+ * path (\"metadata.identity.name\").
+ */";
+
+fn main() {
+    println!("cargo::rerun-if-changed=src/lib.rs");
+    for var in ["PREFIX", "LIBDIR", "INCLUDEDIR"] {
+        println!("cargo::rerun-if-env-changed={var}");
+    }
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"));
+
+    generate_header(&out_dir.join(HEADER));
+    fs::write(out_dir.join(PKG_CONFIG), pkg_config_file()).expect("failed to write os-info.pc");
+}
+
+/// Write the C header for every `extern "C"` function in `src/lib.rs`
+fn generate_header(path: &Path) {
+    let mut config = Config {
+        language: Language::C,
+        header: Some(PREAMBLE.to_string()),
+        include_guard: Some("OS_INFO_H".to_string()),
+        cpp_compat: true,
+        no_includes: true,
+        sys_includes: vec!["stddef.h".to_string()],
+        usize_is_size_t: true,
+        style: Style::Type,
+        ..Config::default()
+    };
+    config.export.rename.extend([
+        ("OsInfoHandle".to_string(), "os_info_t".to_string()),
+        ("OsInfoError".to_string(), "os_info_error_t".to_string()),
+    ]);
+
+    Builder::new()
+        .with_config(config)
+        .with_src("src/lib.rs")
+        .generate()
+        .expect("failed to generate os-info.h")
+        .write_to_file(path);
+}
+
+/// The pkg-config file, with paths from the environment
+fn pkg_config_file() -> String {
+    let var = |name: &str, default: &str| env::var(name).unwrap_or_else(|_| default.to_string());
+    let prefix = var("PREFIX", "/usr/local");
+    let libdir = var("LIBDIR", "${prefix}/lib");
+    let includedir = var("INCLUDEDIR", "${prefix}/include");
+    let version = env!("CARGO_PKG_VERSION");
+
+    format!(
+        "prefix={prefix}
+libdir={libdir}
+includedir={includedir}
+
+Name: os-info
+Description: Structured OS information standard
+URL: https://github.com/AerynOS/os-info
+Version: {version}
+Libs: -L${{libdir}} -los_info_capi
+Libs.private: -lpthread -ldl -lm
+Cflags: -I${{includedir}}
+"
+    )
+}
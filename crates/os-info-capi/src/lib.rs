@@ -0,0 +1,469 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! C ABI for os-info
+//!
+//! Exposes loading, discovery, field lookup and os-release rendering to C and
+//! C++ through the functions declared in `os-info.h`. Documents are
+//! opaque `os_info_t` handles. Strings returned by the library are owned by
+//! the caller and released with [`os_info_string_free`], errors with
+//! [`os_info_error_free`].
+//!
+//! The header is generated from this file by the build script, including the
+//! documentation of each function.
+
+use std::ffi::{CStr, CString, c_char};
+use std::path::Path;
+use std::ptr;
+
+use os_info::{
+    LoadedOsInfo, Loader, OsInfo, OsRelease, error_chain, json_pointer, load_os_info,
+    load_os_info_from_path,
+};
+use serde_json::Value;
+
+/// A loaded os-info document
+pub struct OsInfoHandle {
+    source: Source,
+    document: Value,
+}
+
+/// Where the document of a handle came from
+enum Source {
+    /// Resolved from the layers below a root
+    Loaded(LoadedOsInfo),
+    /// Parsed from a single file or string
+    Parsed(OsInfo),
+}
+
+/// An error raised by a failed call
+pub struct OsInfoError {
+    message: CString,
+}
+
+impl OsInfoHandle {
+    fn new(source: Source) -> Result<Self, String> {
+        let info = match &source {
+            Source::Loaded(loaded) => &loaded.info,
+            Source::Parsed(info) => info,
+        };
+        let document = serde_json::to_value(info).map_err(|err| err.to_string())?;
+        Ok(Self { source, document })
+    }
+
+    fn info(&self) -> &OsInfo {
+        match &self.source {
+            Source::Loaded(loaded) => &loaded.info,
+            Source::Parsed(info) => info,
+        }
+    }
+
+    fn loaded(&self) -> Option<&LoadedOsInfo> {
+        match &self.source {
+            Source::Loaded(loaded) => Some(loaded),
+            Source::Parsed(_) => None,
+        }
+    }
+}
+
+/// Store an error for the caller, if they asked for one
+unsafe fn set_error(error: *mut *mut OsInfoError, message: String) {
+    if error.is_null() {
+        return;
+    }
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    // SAFETY: the caller passes a valid pointer or NULL, checked above
+    unsafe { *error = Box::into_raw(Box::new(OsInfoError { message })) };
+}
+
+/// Hand a handle to the caller, or report why it couldn't be created
+unsafe fn into_handle(
+    result: Result<OsInfoHandle, String>,
+    error: *mut *mut OsInfoError,
+) -> *mut OsInfoHandle {
+    match result {
+        Ok(handle) => Box::into_raw(Box::new(handle)),
+        Err(message) => {
+            // SAFETY: forwarded from the caller
+            unsafe { set_error(error, message) };
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Borrow a C string argument
+unsafe fn str_arg<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    // SAFETY: the caller passes a valid NUL-terminated string
+    unsafe { CStr::from_ptr(s) }.to_str().ok()
+}
+
+/// Hand a string to the caller
+fn into_c_string(s: impl Into<Vec<u8>>) -> *mut c_char {
+    CString::new(s).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Loads and resolves the os-info document below `root`
+///
+/// `root` may be NULL for the running system. On failure NULL is returned and
+/// `error`, if not NULL, receives an error to be freed by the caller.
+///
+/// # Safety
+///
+/// `root` must be NULL or a valid NUL-terminated string, and `error` NULL or
+/// a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_load(
+    root: *const c_char,
+    error: *mut *mut OsInfoError,
+) -> *mut OsInfoHandle {
+    // SAFETY: guaranteed by the caller
+    let root = unsafe { str_arg(root) }.unwrap_or("/");
+    let result = Loader::with_root(root)
+        .load()
+        .map_err(|err| error_chain(&err))
+        .and_then(|loaded| OsInfoHandle::new(Source::Loaded(loaded)));
+    // SAFETY: forwarded from the caller
+    unsafe { into_handle(result, error) }
+}
+
+/// Loads a single os-info document from `path`, without local overrides
+///
+/// # Safety
+///
+/// `path` must be a valid NUL-terminated string, and `error` NULL or a valid
+/// pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_load_file(
+    path: *const c_char,
+    error: *mut *mut OsInfoError,
+) -> *mut OsInfoHandle {
+    // SAFETY: guaranteed by the caller
+    let result = match unsafe { str_arg(path) } {
+        Some(path) => load_os_info_from_path(Path::new(path))
+            .map_err(|err| error_chain(&err))
+            .and_then(|info| OsInfoHandle::new(Source::Parsed(info))),
+        None => Err("path must be a UTF-8 string".to_string()),
+    };
+    // SAFETY: forwarded from the caller
+    unsafe { into_handle(result, error) }
+}
+
+/// Parses an os-info document from a JSON string
+///
+/// # Safety
+///
+/// `json` must be a valid NUL-terminated string, and `error` NULL or a valid
+/// pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_parse(
+    json: *const c_char,
+    error: *mut *mut OsInfoError,
+) -> *mut OsInfoHandle {
+    // SAFETY: guaranteed by the caller
+    let result = match unsafe { str_arg(json) } {
+        Some(json) => load_os_info(json)
+            .map_err(|err| error_chain(&err))
+            .and_then(|info| OsInfoHandle::new(Source::Parsed(info))),
+        None => Err("document must be a UTF-8 string".to_string()),
+    };
+    // SAFETY: forwarded from the caller
+    unsafe { into_handle(result, error) }
+}
+
+/// Frees a handle. NULL is ignored.
+///
+/// # Safety
+///
+/// `info` must be NULL or a handle returned by this library, not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_free(info: *mut OsInfoHandle) {
+    if !info.is_null() {
+        // SAFETY: guaranteed by the caller
+        drop(unsafe { Box::from_raw(info) });
+    }
+}
+
+/// Looks up a field by JSON pointer or dotted path
+///
+/// Strings are returned as-is, numbers and booleans in their JSON form, and
+/// objects and arrays as JSON. Returns NULL when the field is absent or null.
+///
+/// # Safety
+///
+/// `info` must be a valid handle and `path` a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_get(
+    info: *const OsInfoHandle,
+    path: *const c_char,
+) -> *mut c_char {
+    // SAFETY: guaranteed by the caller
+    let (Some(info), Some(path)) = (unsafe { info.as_ref() }, unsafe { str_arg(path) }) else {
+        return ptr::null_mut();
+    };
+    match info.document.pointer(&json_pointer(path)) {
+        None | Some(Value::Null) => ptr::null_mut(),
+        Some(Value::String(s)) => into_c_string(s.as_str()),
+        Some(value) => into_c_string(value.to_string()),
+    }
+}
+
+/// Serialises the whole document as JSON
+///
+/// # Safety
+///
+/// `info` must be a valid handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_to_json(info: *const OsInfoHandle) -> *mut c_char {
+    // SAFETY: guaranteed by the caller
+    match unsafe { info.as_ref() } {
+        Some(info) => into_c_string(info.document.to_string()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Renders the document in os-release format
+///
+/// # Safety
+///
+/// `info` must be a valid handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_os_release(info: *const OsInfoHandle) -> *mut c_char {
+    // SAFETY: guaranteed by the caller
+    match unsafe { info.as_ref() } {
+        Some(info) => into_c_string(OsRelease::from(info.info()).to_string()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Number of layers the document was resolved from
+///
+/// Documents from [`os_info_parse`] and [`os_info_load_file`] have none.
+///
+/// # Safety
+///
+/// `info` must be a valid handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_layer_count(info: *const OsInfoHandle) -> usize {
+    // SAFETY: guaranteed by the caller
+    unsafe { info.as_ref() }
+        .and_then(OsInfoHandle::loaded)
+        .map_or(0, |loaded| loaded.layers.len())
+}
+
+/// Path of the layer at `index`, in the order the layers were applied
+///
+/// # Safety
+///
+/// `info` must be a valid handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_layer_path(
+    info: *const OsInfoHandle,
+    index: usize,
+) -> *mut c_char {
+    // SAFETY: guaranteed by the caller
+    unsafe { info.as_ref() }
+        .and_then(OsInfoHandle::loaded)
+        .and_then(|loaded| loaded.layers.get(index))
+        .map_or(ptr::null_mut(), |layer| {
+            into_c_string(layer.path.to_string_lossy().into_owned())
+        })
+}
+
+/// Path of the layer that provided a field, by JSON pointer or dotted path
+///
+/// # Safety
+///
+/// `info` must be a valid handle and `path` a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_source_of(
+    info: *const OsInfoHandle,
+    path: *const c_char,
+) -> *mut c_char {
+    // SAFETY: guaranteed by the caller
+    let (Some(info), Some(path)) = (unsafe { info.as_ref() }, unsafe { str_arg(path) }) else {
+        return ptr::null_mut();
+    };
+    info.loaded()
+        .and_then(|loaded| loaded.source_of(path))
+        .map_or(ptr::null_mut(), |layer| {
+            into_c_string(layer.path.to_string_lossy().into_owned())
+        })
+}
+
+/// Container runtime of the root the document was loaded from, e.g. `docker`
+///
+/// Returns NULL outside containers and for documents not loaded from a root.
+///
+/// # Safety
+///
+/// `info` must be a valid handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_container(info: *const OsInfoHandle) -> *mut c_char {
+    // SAFETY: guaranteed by the caller
+    unsafe { info.as_ref() }
+        .and_then(OsInfoHandle::loaded)
+        .and_then(|loaded| loaded.environment.container.as_ref())
+        .map_or(ptr::null_mut(), |container| {
+            into_c_string(container.to_string())
+        })
+}
+
+/// Message of an error, valid until the error is freed
+///
+/// # Safety
+///
+/// `error` must be an error returned by this library, not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_error_message(error: *const OsInfoError) -> *const c_char {
+    // SAFETY: guaranteed by the caller
+    match unsafe { error.as_ref() } {
+        Some(error) => error.message.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+/// Frees an error. NULL is ignored.
+///
+/// # Safety
+///
+/// `error` must be NULL or an error returned by this library, not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_error_free(error: *mut OsInfoError) {
+    if !error.is_null() {
+        // SAFETY: guaranteed by the caller
+        drop(unsafe { Box::from_raw(error) });
+    }
+}
+
+/// Frees a string returned by this library. NULL is ignored.
+///
+/// # Safety
+///
+/// `s` must be NULL or a string returned by this library, not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn os_info_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: guaranteed by the caller
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// Version of the library, e.g. `0.1.0`
+#[unsafe(no_mangle)]
+pub extern "C" fn os_info_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Take ownership of a returned string
+    fn take(s: *mut c_char) -> Option<String> {
+        if s.is_null() {
+            return None;
+        }
+        let owned = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+        unsafe { os_info_string_free(s) };
+        Some(owned)
+    }
+
+    #[test]
+    fn test_parse_and_get() {
+        let json = CString::new(include_str!("../../../sample.json")).unwrap();
+        let info = unsafe { os_info_parse(json.as_ptr(), ptr::null_mut()) };
+        assert!(!info.is_null());
+
+        let get = |path: &str| {
+            let path = CString::new(path).unwrap();
+            take(unsafe { os_info_get(info, path.as_ptr()) })
+        };
+        assert_eq!(get("metadata.identity.name").as_deref(), Some("AerynOS"));
+        assert_eq!(get("/metadata/version/short").as_deref(), Some("0.25.1"));
+        assert_eq!(
+            get("system.features.boot.firmware.uefi").as_deref(),
+            Some("true")
+        );
+        assert_eq!(
+            get("system.composition.technology.optional").as_deref(),
+            Some("[\"flatpak\"]")
+        );
+        assert_eq!(get("metadata.identity.missing"), None);
+
+        let os_release = take(unsafe { os_info_os_release(info) }).unwrap();
+        assert!(os_release.contains("ID=\"aerynos\"\n"));
+        assert_eq!(unsafe { os_info_layer_count(info) }, 0);
+
+        unsafe { os_info_free(info) };
+    }
+
+    #[test]
+    fn test_load_and_discovery() {
+        let root = std::env::temp_dir().join(format!("os-info-capi-load-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("usr/lib")).unwrap();
+        std::fs::copy("../../sample.json", root.join("usr/lib/os-info.json")).unwrap();
+
+        let c_root = CString::new(root.to_str().unwrap()).unwrap();
+        let info = unsafe { os_info_load(c_root.as_ptr(), ptr::null_mut()) };
+        assert!(!info.is_null());
+        assert_eq!(unsafe { os_info_layer_count(info) }, 1);
+
+        let vendor = root.join("usr/lib/os-info.json").display().to_string();
+        assert_eq!(
+            take(unsafe { os_info_layer_path(info, 0) }).as_deref(),
+            Some(vendor.as_str())
+        );
+        let path = CString::new("metadata.identity.id").unwrap();
+        assert_eq!(
+            take(unsafe { os_info_source_of(info, path.as_ptr()) }).as_deref(),
+            Some(vendor.as_str())
+        );
+        assert_eq!(take(unsafe { os_info_container(info) }), None);
+
+        unsafe { os_info_free(info) };
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let json = CString::new("{\"metadata\": {}}").unwrap();
+        let mut error = ptr::null_mut();
+        let info = unsafe { os_info_parse(json.as_ptr(), &mut error) };
+        assert!(info.is_null());
+        assert!(!error.is_null());
+
+        let message = unsafe { CStr::from_ptr(os_info_error_message(error)) };
+        assert!(
+            message
+                .to_str()
+                .unwrap()
+                .starts_with("failed to parse JSON: ")
+        );
+        unsafe { os_info_error_free(error) };
+
+        // Callers may ignore errors
+        assert!(unsafe { os_info_parse(json.as_ptr(), ptr::null_mut()) }.is_null());
+    }
+
+    #[test]
+    fn test_header_declares_exports() {
+        let header = include_str!(concat!(env!("OUT_DIR"), "/os-info.h"));
+        let exports = include_str!("lib.rs")
+            .lines()
+            .filter_map(|line| line.split("extern \"C\" fn ").nth(1))
+            .filter_map(|rest| rest.split('(').next())
+            .collect::<Vec<_>>();
+
+        assert!(exports.len() > 10);
+        for name in exports {
+            assert!(
+                header.contains(&format!(" {name}(")) || header.contains(&format!("*{name}(")),
+                "{name} is missing from os-info.h"
+            );
+        }
+    }
+}
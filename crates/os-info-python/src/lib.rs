@@ -0,0 +1,1055 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Python bindings for os-info
+//!
+//! Builds the `os_info` extension module, so scripts read documents with the
+//! same loader, parser and os-release conversion as the Rust tools:
+//!
+//! ```python
+//! import os_info
+//!
+//! loaded = os_info.load()
+//! print(loaded.info.identity.display)
+//! print(loaded.info.os_release())
+//! ```
+//!
+//! Documents are exposed as read-only classes with properties that share one
+//! parsed document, so looking up a property doesn't copy the whole tree.
+//! `get()` and `to_dict()` read its JSON form, serialised once on first use.
+//!
+//! The classes cover identity, version, system and features, maintainers,
+//! websites, social and funding links, and the security contact. Other fields
+//! are reached through `get()`.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use chrono::{DateTime, Utc};
+use os_info_rs::{
+    FundingLink as RsFundingLink, Layer as RsLayer, LayerKind, LoadedOsInfo as RsLoadedOsInfo,
+    Loader, Maintainer as RsMaintainer, OsInfo as RsOsInfo, OsRelease, SocialLink as RsSocialLink,
+    Website as RsWebsite, error_chain, json_pointer, load_os_info, load_os_info_from_path,
+    locales_from_env,
+};
+use pyo3::IntoPyObjectExt;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use serde::Serialize;
+use serde_json::Value;
+
+create_exception!(
+    os_info,
+    OsInfoError,
+    PyException,
+    "Raised when a document can't be loaded or parsed"
+);
+
+fn to_py_err(err: impl Error) -> PyErr {
+    OsInfoError::new_err(error_chain(&err))
+}
+
+/// Serialised name of a unit enum, e.g. `bug-tracker`
+fn name_of(value: &impl Serialize) -> String {
+    match serde_json::to_value(value) {
+        Ok(Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+/// Convert a JSON value into the matching Python object
+fn to_python(py: Python<'_>, value: &Value) -> PyResult<Py<PyAny>> {
+    match value {
+        Value::Null => Ok(py.None()),
+        Value::Bool(b) => b.into_py_any(py),
+        Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(i), _) => i.into_py_any(py),
+            (_, Some(u)) => u.into_py_any(py),
+            _ => n.as_f64().unwrap_or_default().into_py_any(py),
+        },
+        Value::String(s) => s.into_py_any(py),
+        Value::Array(items) => {
+            let list = PyList::empty(py);
+            for item in items {
+                list.append(to_python(py, item)?)?;
+            }
+            list.into_py_any(py)
+        }
+        Value::Object(fields) => {
+            let dict = PyDict::new(py);
+            for (key, item) in fields {
+                dict.set_item(key, to_python(py, item)?)?;
+            }
+            dict.into_py_any(py)
+        }
+    }
+}
+
+/// Locales to resolve translations for, the environment's when not given
+fn locales(locales: Option<Vec<String>>) -> Vec<String> {
+    locales.unwrap_or_else(locales_from_env)
+}
+
+/// A parsed document, shared by every object viewing into it
+#[derive(Debug, Clone)]
+struct Document {
+    source: Source,
+    /// JSON form of the document, serialised on first use
+    json: Arc<OnceLock<Value>>,
+}
+
+/// Where a document came from
+#[derive(Debug, Clone)]
+enum Source {
+    /// Resolved from the layers below a root
+    Loaded(Arc<RsLoadedOsInfo>),
+    /// Parsed from a single file or string
+    Parsed(Arc<RsOsInfo>),
+}
+
+impl Document {
+    fn new(source: Source) -> Self {
+        Self {
+            source,
+            json: Arc::default(),
+        }
+    }
+
+    fn info(&self) -> &RsOsInfo {
+        match &self.source {
+            Source::Loaded(loaded) => &loaded.info,
+            Source::Parsed(info) => info,
+        }
+    }
+
+    fn json(&self) -> PyResult<&Value> {
+        if let Some(json) = self.json.get() {
+            return Ok(json);
+        }
+        let json = serde_json::to_value(self.info()).map_err(to_py_err)?;
+        Ok(self.json.get_or_init(|| json))
+    }
+}
+
+/// An os-info document
+#[pyclass(name = "OsInfo", module = "os_info", frozen)]
+struct OsInfo {
+    document: Document,
+}
+
+#[pymethods]
+impl OsInfo {
+    /// Schema version of the document
+    #[getter]
+    fn os_info_version(&self) -> &str {
+        &self.document.info().version
+    }
+
+    /// When the OS project started
+    #[getter]
+    fn start_date(&self) -> DateTime<Utc> {
+        self.document.info().start_date
+    }
+
+    #[getter]
+    fn identity(&self) -> Identity {
+        Identity {
+            document: self.document.clone(),
+        }
+    }
+
+    #[getter]
+    fn version(&self) -> Version {
+        Version {
+            document: self.document.clone(),
+        }
+    }
+
+    #[getter]
+    fn system(&self) -> System {
+        System {
+            document: self.document.clone(),
+        }
+    }
+
+    /// Maintainers by group, e.g. `{"founders": [...]}`
+    #[getter]
+    fn maintainers(&self) -> BTreeMap<String, Vec<Maintainer>> {
+        self.document
+            .info()
+            .metadata
+            .maintainers
+            .iter()
+            .map(|(group, maintainers)| {
+                let maintainers = (0..maintainers.len())
+                    .map(|index| Maintainer {
+                        document: self.document.clone(),
+                        group: group.clone(),
+                        index,
+                    })
+                    .collect();
+                (group.clone(), maintainers)
+            })
+            .collect()
+    }
+
+    /// Websites by key, e.g. `{"homepage": ...}`
+    #[getter]
+    fn websites(&self) -> BTreeMap<String, Website> {
+        self.document
+            .info()
+            .resources
+            .websites
+            .keys()
+            .map(|key| {
+                let website = Website {
+                    document: self.document.clone(),
+                    key: key.clone(),
+                };
+                (key.clone(), website)
+            })
+            .collect()
+    }
+
+    /// Social links by key, e.g. `{"matrix": ...}`
+    #[getter]
+    fn social(&self) -> BTreeMap<String, SocialLink> {
+        self.document
+            .info()
+            .resources
+            .social
+            .keys()
+            .map(|key| {
+                let link = SocialLink {
+                    document: self.document.clone(),
+                    key: key.clone(),
+                };
+                (key.clone(), link)
+            })
+            .collect()
+    }
+
+    /// Funding links by key, e.g. `{"github": ...}`
+    #[getter]
+    fn funding(&self) -> BTreeMap<String, FundingLink> {
+        self.document
+            .info()
+            .resources
+            .funding
+            .keys()
+            .map(|key| {
+                let link = FundingLink {
+                    document: self.document.clone(),
+                    key: key.clone(),
+                };
+                (key.clone(), link)
+            })
+            .collect()
+    }
+
+    /// Where to report vulnerabilities, or `None`
+    #[getter]
+    fn security_contact(&self) -> Option<SecurityContact> {
+        self.document
+            .info()
+            .security_contact
+            .as_ref()
+            .map(|_| SecurityContact {
+                document: self.document.clone(),
+            })
+    }
+
+    /// CPE 2.3 name of the release, when the document names a vendor
+    #[getter]
+    fn cpe(&self) -> Option<String> {
+        self.document.info().cpe().map(|cpe| cpe.to_string())
+    }
+
+    /// Package URL of the release, when the document names a vendor
+    #[getter]
+    fn purl(&self) -> Option<String> {
+        self.document.info().purl().map(|purl| purl.to_string())
+    }
+
+    /// Looks up a field by JSON pointer or dotted path
+    ///
+    /// Returns `None` when the field is absent.
+    fn get(&self, py: Python<'_>, path: &str) -> PyResult<Py<PyAny>> {
+        match self.document.json()?.pointer(&json_pointer(path)) {
+            Some(value) => to_python(py, value),
+            None => Ok(py.None()),
+        }
+    }
+
+    /// The document as plain dicts and lists
+    fn to_dict(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        to_python(py, self.document.json()?)
+    }
+
+    /// Serialises the document as JSON, pretty printed when `indent` is set
+    #[pyo3(signature = (indent=false))]
+    fn to_json(&self, indent: bool) -> PyResult<String> {
+        let info = self.document.info();
+        let json = if indent {
+            serde_json::to_string_pretty(info)
+        } else {
+            serde_json::to_string(info)
+        };
+        json.map_err(to_py_err)
+    }
+
+    /// Renders the document in os-release format
+    fn os_release(&self) -> String {
+        OsRelease::from(self.document.info()).to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        let identity = &self.document.info().metadata.identity;
+        format!(
+            "<OsInfo {} {}>",
+            identity.id,
+            self.document.info().metadata.version.full
+        )
+    }
+}
+
+/// Name and branding of the OS
+#[pyclass(module = "os_info", frozen)]
+struct Identity {
+    document: Document,
+}
+
+impl Identity {
+    fn identity(&self) -> &os_info_rs::Identity {
+        &self.document.info().metadata.identity
+    }
+}
+
+#[pymethods]
+impl Identity {
+    #[getter]
+    fn id(&self) -> &str {
+        &self.identity().id
+    }
+
+    #[getter]
+    fn id_like(&self) -> Option<&str> {
+        self.identity().id_like.as_deref()
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        &self.identity().name
+    }
+
+    #[getter]
+    fn display(&self) -> &str {
+        &self.identity().display
+    }
+
+    #[getter]
+    fn ansi_color(&self) -> Option<&str> {
+        self.identity().ansi_color.as_deref()
+    }
+
+    #[getter]
+    fn vendor(&self) -> Option<&str> {
+        self.identity().vendor.as_deref()
+    }
+
+    /// Name translated for the first matching locale
+    ///
+    /// Locales default to those of the environment, e.g. `LANGUAGE`.
+    #[pyo3(signature = (locales=None))]
+    fn localized_name(&self, locales: Option<Vec<String>>) -> &str {
+        self.identity().localized_name(&self::locales(locales))
+    }
+
+    /// Display name translated for the first matching locale
+    #[pyo3(signature = (locales=None))]
+    fn localized_display(&self, locales: Option<Vec<String>>) -> &str {
+        self.identity().localized_display(&self::locales(locales))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Identity {}>", self.identity().id)
+    }
+}
+
+/// Version of the release
+#[pyclass(module = "os_info", frozen)]
+struct Version {
+    document: Document,
+}
+
+impl Version {
+    fn version(&self) -> &os_info_rs::VersionInfo {
+        &self.document.info().metadata.version
+    }
+}
+
+#[pymethods]
+impl Version {
+    #[getter]
+    fn full(&self) -> &str {
+        &self.version().full
+    }
+
+    #[getter]
+    fn short(&self) -> &str {
+        &self.version().short
+    }
+
+    #[getter]
+    fn build_id(&self) -> &str {
+        &self.version().build_id
+    }
+
+    #[getter]
+    fn released(&self) -> DateTime<Utc> {
+        self.version().released
+    }
+
+    #[getter]
+    fn codename(&self) -> Option<&str> {
+        self.version().codename.as_deref()
+    }
+
+    #[getter]
+    fn announcement(&self) -> Option<String> {
+        self.version()
+            .announcement
+            .as_ref()
+            .map(ToString::to_string)
+    }
+
+    #[getter]
+    fn sysext_level(&self) -> Option<&str> {
+        self.version().sysext_level.as_deref()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Version {}>", self.version().full)
+    }
+}
+
+/// Platform, kernel and composition of the OS
+#[pyclass(module = "os_info", frozen)]
+struct System {
+    document: Document,
+}
+
+impl System {
+    fn system(&self) -> &os_info_rs::System {
+        &self.document.info().system
+    }
+}
+
+#[pymethods]
+impl System {
+    #[getter]
+    fn architecture(&self) -> &str {
+        &self.system().platform.architecture
+    }
+
+    #[getter]
+    fn variant(&self) -> &str {
+        &self.system().platform.variant
+    }
+
+    #[getter]
+    fn kernel_name(&self) -> &str {
+        &self.system().kernel.name
+    }
+
+    #[getter]
+    fn kernel_type(&self) -> &str {
+        &self.system().kernel.kernel_type
+    }
+
+    #[getter]
+    fn bases(&self) -> Vec<String> {
+        self.system().composition.bases.clone()
+    }
+
+    #[getter]
+    fn core_technologies(&self) -> Vec<String> {
+        self.system().composition.technology.core.clone()
+    }
+
+    #[getter]
+    fn optional_technologies(&self) -> Vec<String> {
+        self.system().composition.technology.optional.clone()
+    }
+
+    #[getter]
+    fn features(&self) -> Features {
+        Features {
+            document: self.document.clone(),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<System {}>", self.system().platform.architecture)
+    }
+}
+
+/// Update, boot and filesystem features of the OS
+#[pyclass(module = "os_info", frozen)]
+struct Features {
+    document: Document,
+}
+
+impl Features {
+    fn features(&self) -> &os_info_rs::Features {
+        &self.document.info().system.features
+    }
+}
+
+#[pymethods]
+impl Features {
+    /// Atomic update strategy, e.g. `immediate`
+    #[getter]
+    fn update_strategy(&self) -> &str {
+        &self.features().atomic_updates.strategy
+    }
+
+    #[getter]
+    fn rollback_support(&self) -> bool {
+        self.features().atomic_updates.rollback_support
+    }
+
+    #[getter]
+    fn bootloader(&self) -> &str {
+        &self.features().boot.bootloader
+    }
+
+    #[getter]
+    fn uefi(&self) -> bool {
+        self.features().boot.firmware.uefi
+    }
+
+    #[getter]
+    fn secure_boot(&self) -> bool {
+        self.features().boot.firmware.secure_boot
+    }
+
+    #[getter]
+    fn bios(&self) -> bool {
+        self.features().boot.firmware.bios
+    }
+
+    /// Default filesystem, e.g. `xfs`
+    #[getter]
+    fn filesystem(&self) -> &str {
+        &self.features().filesystem.default
+    }
+
+    #[getter]
+    fn supported_filesystems(&self) -> Vec<String> {
+        self.features().filesystem.supported.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Features {}>", self.features().atomic_updates.strategy)
+    }
+}
+
+/// A project maintainer
+#[pyclass(module = "os_info", frozen)]
+struct Maintainer {
+    document: Document,
+    group: String,
+    index: usize,
+}
+
+impl Maintainer {
+    fn maintainer(&self) -> &RsMaintainer {
+        &self.document.info().metadata.maintainers[&self.group][self.index]
+    }
+}
+
+#[pymethods]
+impl Maintainer {
+    #[getter]
+    fn name(&self) -> &str {
+        &self.maintainer().name
+    }
+
+    /// Role in the project, e.g. `founder`
+    #[getter]
+    fn role(&self) -> String {
+        name_of(&self.maintainer().role)
+    }
+
+    /// Email address or team page
+    #[getter]
+    fn email(&self) -> String {
+        self.maintainer().email.to_string()
+    }
+
+    #[getter]
+    fn start_date(&self) -> Option<DateTime<Utc>> {
+        self.maintainer().start_date
+    }
+
+    #[getter]
+    fn end_date(&self) -> Option<DateTime<Utc>> {
+        self.maintainer().end_date.into_option()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Maintainer {}>", self.maintainer().name)
+    }
+}
+
+/// A project website
+#[pyclass(module = "os_info", frozen)]
+struct Website {
+    document: Document,
+    key: String,
+}
+
+impl Website {
+    fn website(&self) -> &RsWebsite {
+        &self.document.info().resources.websites[&self.key]
+    }
+}
+
+#[pymethods]
+impl Website {
+    #[getter]
+    fn url(&self) -> String {
+        self.website().url.to_string()
+    }
+
+    #[getter]
+    fn display_name(&self) -> &str {
+        &self.website().display_name
+    }
+
+    /// Purpose of the website, e.g. `bug-tracker`
+    #[getter]
+    fn scope(&self) -> String {
+        name_of(&self.website().scope)
+    }
+
+    /// Display name translated for the first matching locale
+    #[pyo3(signature = (locales=None))]
+    fn localized_display_name(&self, locales: Option<Vec<String>>) -> &str {
+        self.website()
+            .localized_display_name(&self::locales(locales))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Website {}>", self.website().url)
+    }
+}
+
+/// A social media link
+#[pyclass(module = "os_info", frozen)]
+struct SocialLink {
+    document: Document,
+    key: String,
+}
+
+impl SocialLink {
+    fn link(&self) -> &RsSocialLink {
+        &self.document.info().resources.social[&self.key]
+    }
+}
+
+#[pymethods]
+impl SocialLink {
+    #[getter]
+    fn url(&self) -> &str {
+        self.link().url.as_str()
+    }
+
+    #[getter]
+    fn display_name(&self) -> &str {
+        &self.link().display_name
+    }
+
+    /// Platform of the link, e.g. `matrix`
+    #[getter]
+    fn platform(&self) -> &str {
+        self.link().platform.as_str()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<SocialLink {}>", self.link().url)
+    }
+}
+
+/// A funding platform link
+#[pyclass(module = "os_info", frozen)]
+struct FundingLink {
+    document: Document,
+    key: String,
+}
+
+impl FundingLink {
+    fn link(&self) -> &RsFundingLink {
+        &self.document.info().resources.funding[&self.key]
+    }
+}
+
+#[pymethods]
+impl FundingLink {
+    #[getter]
+    fn url(&self) -> &str {
+        self.link().url.as_str()
+    }
+
+    #[getter]
+    fn display_name(&self) -> &str {
+        &self.link().display_name
+    }
+
+    /// Platform of the link, e.g. `github`
+    #[getter]
+    fn platform(&self) -> &str {
+        self.link().platform.as_str()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<FundingLink {}>", self.link().url)
+    }
+}
+
+/// Where to report vulnerabilities
+#[pyclass(module = "os_info", frozen)]
+struct SecurityContact {
+    document: Document,
+}
+
+impl SecurityContact {
+    fn contact(&self) -> &os_info_rs::SecurityContact {
+        self.document
+            .info()
+            .security_contact
+            .as_ref()
+            .expect("only created for documents with a security contact")
+    }
+}
+
+#[pymethods]
+impl SecurityContact {
+    #[getter]
+    fn email(&self) -> Option<&str> {
+        self.contact().email.as_ref().map(|email| email.as_str())
+    }
+
+    /// PGP key as written, e.g. a fingerprint or key URL
+    #[getter]
+    fn pgp_key(&self) -> Option<&str> {
+        self.contact().pgp_key.as_ref().map(|key| key.as_str())
+    }
+
+    #[getter]
+    fn disclosure_policy(&self) -> Option<&str> {
+        self.contact().disclosure_policy.as_deref()
+    }
+
+    fn __repr__(&self) -> String {
+        let email = self.email().unwrap_or_default();
+        format!("<SecurityContact {email}>")
+    }
+}
+
+/// A document resolved from the layers below a root
+#[pyclass(name = "LoadedOsInfo", module = "os_info", frozen)]
+struct LoadedOsInfo {
+    loaded: Arc<RsLoadedOsInfo>,
+    document: Document,
+}
+
+#[pymethods]
+impl LoadedOsInfo {
+    /// The resolved document
+    #[getter]
+    fn info(&self) -> OsInfo {
+        OsInfo {
+            document: self.document.clone(),
+        }
+    }
+
+    /// Layers in the order they were applied
+    #[getter]
+    fn layers(&self) -> Vec<Layer> {
+        self.loaded.layers.iter().map(Layer::from).collect()
+    }
+
+    /// Container runtime of the root, e.g. `docker`, or `None`
+    #[getter]
+    fn container(&self) -> Option<String> {
+        self.loaded
+            .environment
+            .container
+            .as_ref()
+            .map(ToString::to_string)
+    }
+
+    /// Whether the root is a chroot of another system
+    #[getter]
+    fn chroot(&self) -> bool {
+        self.loaded.environment.chroot
+    }
+
+    /// Layer that provided a field, by JSON pointer or dotted path
+    fn source_of(&self, path: &str) -> Option<Layer> {
+        self.loaded.source_of(path).map(Layer::from)
+    }
+
+    fn __repr__(&self) -> String {
+        let info = &self.loaded.info;
+        format!(
+            "<LoadedOsInfo {} {} from {} layers>",
+            info.metadata.identity.id,
+            info.metadata.version.full,
+            self.loaded.layers.len()
+        )
+    }
+}
+
+/// A single document that contributed to a loaded os-info
+#[pyclass(module = "os_info", frozen)]
+struct Layer {
+    kind: LayerKind,
+    path: PathBuf,
+    signed_by: Option<String>,
+}
+
+impl From<&RsLayer> for Layer {
+    fn from(layer: &RsLayer) -> Self {
+        Self {
+            kind: layer.kind,
+            path: layer.path.clone(),
+            signed_by: layer.signed_by.as_ref().map(ToString::to_string),
+        }
+    }
+}
+
+#[pymethods]
+impl Layer {
+    /// `vendor`, `local` or `drop-in`
+    #[getter]
+    fn kind(&self) -> &'static str {
+        match self.kind {
+            LayerKind::Vendor => "vendor",
+            LayerKind::Local => "local",
+            LayerKind::DropIn => "drop-in",
+        }
+    }
+
+    #[getter]
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Key ID that signed the layer, when loaded with `verified=True`
+    #[getter]
+    fn signed_by(&self) -> Option<&str> {
+        self.signed_by.as_deref()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Layer {} {}>", self.kind(), self.path.display())
+    }
+}
+
+/// Loads and resolves the document below `root`, or of the running system
+///
+/// With `verified=True` every layer must carry a valid signature from a
+/// trusted key.
+#[pyfunction]
+#[pyo3(signature = (root=None, verified=false))]
+fn load(root: Option<PathBuf>, verified: bool) -> PyResult<LoadedOsInfo> {
+    let mut loader = match root {
+        Some(root) => Loader::with_root(root),
+        None => Loader::new(),
+    };
+    if verified {
+        loader = loader.verified();
+    }
+    let loaded = Arc::new(loader.load().map_err(to_py_err)?);
+    Ok(LoadedOsInfo {
+        document: Document::new(Source::Loaded(loaded.clone())),
+        loaded,
+    })
+}
+
+/// Loads a single document from `path`, without local overrides
+#[pyfunction]
+fn load_file(path: PathBuf) -> PyResult<OsInfo> {
+    let info = load_os_info_from_path(path).map_err(to_py_err)?;
+    Ok(OsInfo {
+        document: Document::new(Source::Parsed(Arc::new(info))),
+    })
+}
+
+/// Parses a document from a JSON string
+#[pyfunction]
+fn loads(json: &str) -> PyResult<OsInfo> {
+    let info = load_os_info(json).map_err(to_py_err)?;
+    Ok(OsInfo {
+        document: Document::new(Source::Parsed(Arc::new(info))),
+    })
+}
+
+/// Checks a JSON string is a valid document, raising `OsInfoError` if not
+///
+/// The document must parse into the typed model, which checks required
+/// fields, dates, URLs and email addresses, and pass `OsInfo::validate`,
+/// which checks funding and social links against their platforms and that
+/// contacts are usable. It isn't checked against the JSON schema.
+#[pyfunction]
+fn validate(json: &str) -> PyResult<()> {
+    load_os_info(json)
+        .map_err(to_py_err)?
+        .validate()
+        .map_err(to_py_err)
+}
+
+#[pymodule]
+fn os_info(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add("OsInfoError", m.py().get_type::<OsInfoError>())?;
+    m.add_class::<OsInfo>()?;
+    m.add_class::<Identity>()?;
+    m.add_class::<Version>()?;
+    m.add_class::<System>()?;
+    m.add_class::<Features>()?;
+    m.add_class::<Maintainer>()?;
+    m.add_class::<Website>()?;
+    m.add_class::<SocialLink>()?;
+    m.add_class::<FundingLink>()?;
+    m.add_class::<SecurityContact>()?;
+    m.add_class::<LoadedOsInfo>()?;
+    m.add_class::<Layer>()?;
+    m.add_function(wrap_pyfunction!(load, m)?)?;
+    m.add_function(wrap_pyfunction!(load_file, m)?)?;
+    m.add_function(wrap_pyfunction!(loads, m)?)?;
+    m.add_function(wrap_pyfunction!(validate, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+    use std::fs;
+
+    use super::*;
+
+    /// Runs a Python snippet with the module imported as `os_info`
+    fn run(script: &str, globals: impl FnOnce(&Bound<'_, PyDict>)) {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "os_info").unwrap();
+            os_info(&module).unwrap();
+            let dict = PyDict::new(py);
+            dict.set_item("os_info", module).unwrap();
+            globals(&dict);
+            let script = CString::new(script).unwrap();
+            if let Err(err) = py.run(&script, Some(&dict), None) {
+                err.display(py);
+                panic!("script failed");
+            }
+        });
+    }
+
+    #[test]
+    fn test_loads() {
+        run(
+            r#"
+info = os_info.loads(sample)
+assert info.identity.id == "aerynos"
+assert info.identity.display == "AerynOS 0.25.1"
+assert info.version.short == info.get("metadata.version.short")
+assert info.version.released.year >= 2025
+assert info.get("/metadata/identity/id") == "aerynos"
+assert info.get("metadata.missing") is None
+assert info.system.architecture == info.to_dict()["system"]["platform"]["architecture"]
+assert all(m.name for group in info.maintainers.values() for m in group)
+assert all(site.url.startswith("https://") for site in info.websites.values())
+assert 'PRETTY_NAME="AerynOS 0.25.1"\n' in info.os_release()
+assert os_info.loads(info.to_json()).identity.id == "aerynos"
+
+features = info.system.features
+assert features.update_strategy == "immediate" and features.rollback_support
+assert features.uefi and not features.bios
+assert features.filesystem in features.supported_filesystems
+assert info.social["matrix"].platform == "matrix"
+assert info.funding["kofi"].url == "https://ko-fi.com/AerynOS"
+assert info.security_contact is None
+
+import json
+document = json.loads(sample)
+document["security_contact"] = {"email": "security@aerynos.com"}
+contact = os_info.loads(json.dumps(document)).security_contact
+assert contact.email == "security@aerynos.com" and contact.pgp_key is None
+
+document["resources"]["funding"]["kofi"]["url"] = "https://example.com/AerynOS"
+os_info.loads(json.dumps(document))
+try:
+    os_info.validate(json.dumps(document))
+    raise AssertionError("mismatched funding link accepted")
+except os_info.OsInfoError as err:
+    assert str(err).startswith("invalid funding link: "), str(err)
+
+del document["resources"]["funding"]["kofi"]
+document["security_contact"] = {"disclosure_policy": "https://aerynos.com/security"}
+try:
+    os_info.validate(json.dumps(document))
+    raise AssertionError("security contact without email accepted")
+except os_info.OsInfoError as err:
+    assert str(err) == "security contact has no email address", str(err)
+
+try:
+    os_info.validate("{")
+    raise AssertionError("invalid document accepted")
+except os_info.OsInfoError as err:
+    assert str(err).startswith("failed to parse JSON: "), str(err)
+"#,
+            |globals| {
+                globals
+                    .set_item("sample", include_str!("../../../sample.json"))
+                    .unwrap()
+            },
+        );
+    }
+
+    #[test]
+    fn test_load() {
+        let root = std::env::temp_dir().join(format!("os-info-python-load-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("usr/lib")).unwrap();
+        fs::create_dir_all(root.join("etc")).unwrap();
+        fs::copy("../../sample.json", root.join(os_info_rs::VENDOR_PATH)).unwrap();
+        fs::write(
+            root.join(os_info_rs::LOCAL_PATH),
+            r#"{"metadata": {"identity": {"display": "Local OS"}}}"#,
+        )
+        .unwrap();
+
+        run(
+            r#"
+loaded = os_info.load(root)
+assert loaded.info.identity.display == "Local OS"
+assert [layer.kind for layer in loaded.layers] == ["vendor", "local"]
+assert loaded.source_of("metadata.identity.display").kind == "local"
+assert loaded.source_of("/metadata/identity/id").path.name == "os-info.json"
+assert loaded.container is None
+"#,
+            |globals| globals.set_item("root", &root).unwrap(),
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,393 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Conversion support for AppStream metainfo
+//!
+//! This module converts an [`OsInfo`] into an AppStream `operating-system`
+//! component, as used by software centres and fwupd to describe the running
+//! system.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+use chrono::{DateTime, Utc};
+
+use crate::page::escape_markup;
+use crate::{OsInfo, Url, WebsiteScope};
+
+/// Metadata license written unless overridden
+pub const DEFAULT_METADATA_LICENSE: &str = "CC0-1.0";
+
+/// AppStream URL types relevant to operating systems
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AppStreamUrlType {
+    /// Project homepage
+    Homepage,
+    /// Bug tracker
+    BugTracker,
+    /// User help and documentation
+    Help,
+    /// Donation page
+    Donation,
+    /// Contact and support page
+    Contact,
+    /// Source code browser
+    VcsBrowser,
+    /// Contributor guide
+    Contribute,
+}
+
+impl AppStreamUrlType {
+    /// Value of the `type` attribute
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AppStreamUrlType::Homepage => "homepage",
+            AppStreamUrlType::BugTracker => "bugtracker",
+            AppStreamUrlType::Help => "help",
+            AppStreamUrlType::Donation => "donation",
+            AppStreamUrlType::Contact => "contact",
+            AppStreamUrlType::VcsBrowser => "vcs-browser",
+            AppStreamUrlType::Contribute => "contribute",
+        }
+    }
+
+    /// Maps a website scope to its AppStream URL type, if one exists
+    pub fn from_scope(scope: WebsiteScope) -> Option<Self> {
+        match scope {
+            WebsiteScope::Home => Some(AppStreamUrlType::Homepage),
+            WebsiteScope::BugTracker => Some(AppStreamUrlType::BugTracker),
+            WebsiteScope::Documentation | WebsiteScope::EndUserDocs => Some(AppStreamUrlType::Help),
+            WebsiteScope::Support => Some(AppStreamUrlType::Contact),
+            WebsiteScope::Developer => Some(AppStreamUrlType::VcsBrowser),
+            WebsiteScope::DeveloperDocs => Some(AppStreamUrlType::Contribute),
+            WebsiteScope::Public
+            | WebsiteScope::PrivacyPolicy
+            | WebsiteScope::TermsOfService
+            | WebsiteScope::Legal
+            | WebsiteScope::SecurityPolicy => None,
+        }
+    }
+}
+
+/// A release entry in the component history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppStreamRelease {
+    /// Release version
+    pub version: String,
+    /// Release date
+    pub date: DateTime<Utc>,
+    /// Short description of the release
+    pub description: Option<String>,
+    /// Link to the release announcement
+    pub url: Option<String>,
+}
+
+/// Represents an AppStream `operating-system` component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppStreamComponent {
+    /// Reverse-DNS component identifier
+    pub id: String,
+    /// License of the metadata itself
+    pub metadata_license: String,
+    /// Human readable name
+    pub name: String,
+    /// Translated names, keyed by locale
+    pub name_translations: BTreeMap<String, String>,
+    /// One line summary, required by AppStream
+    ///
+    /// Documents don't carry one, so it defaults to the display name and
+    /// callers with a better description should replace it.
+    pub summary: String,
+    /// Translated summaries, keyed by locale
+    pub summary_translations: BTreeMap<String, String>,
+    /// Longer description paragraphs
+    pub description: Vec<String>,
+    /// Developer name
+    pub developer: Option<String>,
+    /// Project links, at most one per type
+    pub urls: BTreeMap<AppStreamUrlType, String>,
+    /// Release history, newest first
+    pub releases: Vec<AppStreamRelease>,
+}
+
+/// Convert an OSInfo into an AppStream component
+impl From<&OsInfo> for AppStreamComponent {
+    fn from(info: &OsInfo) -> Self {
+        let identity = &info.metadata.identity;
+        let version = &info.metadata.version;
+
+        // Scopes declared first take precedence when several map to the same type
+        let mut sites = info.resources.websites.iter().collect::<Vec<_>>();
+        sites.sort_by(|(ka, a), (kb, b)| b.scope.cmp(&a.scope).then(kb.cmp(ka)));
+        let mut urls = BTreeMap::new();
+        for (_, site) in sites {
+            if let Some(url_type) = AppStreamUrlType::from_scope(site.scope) {
+                urls.insert(url_type, site.url.to_string());
+            }
+        }
+        if let Some((_, funding)) = info.resources.funding.iter().min_by_key(|(k, _)| *k) {
+            urls.insert(AppStreamUrlType::Donation, funding.url.to_string());
+        }
+
+        let mut releases = vec![AppStreamRelease {
+            version: version.full.clone(),
+            date: version.released,
+            description: version.codename.clone().into_option(),
+            url: version.announcement.as_ref().map(Url::to_string),
+        }];
+        releases.extend(identity.former_identities.iter().filter_map(|former| {
+            Some(AppStreamRelease {
+                version: former.end_version.clone()?,
+                date: former.end_date,
+                description: Some(format!("Final release as {}", former.name)),
+                url: former.announcement.as_ref().map(Url::to_string),
+            })
+        }));
+        releases.sort_by_key(|r| std::cmp::Reverse(r.date));
+
+        // Websites are a map, so pick the first home page by key to keep the
+        // id stable
+        let home = info
+            .resources
+            .websites
+            .iter()
+            .filter(|(_, site)| site.scope == WebsiteScope::Home)
+            .min_by_key(|(key, _)| *key)
+            .map(|(_, site)| site);
+
+        Self {
+            id: component_id(home.map(|site| site.url.as_str()), &identity.id),
+            metadata_license: DEFAULT_METADATA_LICENSE.to_string(),
+            name: identity.name.clone(),
+            name_translations: translations(info, "name"),
+            summary: identity.display.clone(),
+            summary_translations: translations(info, "display"),
+            description: vec![],
+            developer: None,
+            urls,
+            releases,
+        }
+    }
+}
+
+fn translations(info: &OsInfo, field: &str) -> BTreeMap<String, String> {
+    info.metadata
+        .identity
+        .translations
+        .of(field)
+        .map(|(locale, text)| (locale.to_string(), text.to_string()))
+        .collect()
+}
+
+/// Derive a reverse-DNS id from the homepage, e.g. `com.aerynos.aerynos`
+fn component_id(homepage: Option<&str>, id: &str) -> String {
+    let host = homepage
+        .and_then(|url| url.split_once("://"))
+        .map(|(_, rest)| rest.split(['/', ':', '?', '#']).next().unwrap_or_default())
+        .map(|host| host.trim_start_matches("www."))
+        .filter(|host| !host.is_empty());
+
+    match host {
+        Some(host) => {
+            let mut parts = host.split('.').rev().collect::<Vec<_>>();
+            parts.push(id);
+            parts.join(".")
+        }
+        None => id.to_string(),
+    }
+}
+
+impl Display for AppStreamComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let e = escape_markup;
+
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(f, r#"<component type="operating-system">"#)?;
+        writeln!(f, "  <id>{}</id>", e(&self.id))?;
+        writeln!(
+            f,
+            "  <metadata_license>{}</metadata_license>",
+            e(&self.metadata_license)
+        )?;
+        writeln!(f, "  <name>{}</name>", e(&self.name))?;
+        for (locale, name) in &self.name_translations {
+            writeln!(f, r#"  <name xml:lang="{}">{}</name>"#, e(locale), e(name))?;
+        }
+        writeln!(f, "  <summary>{}</summary>", e(&self.summary))?;
+        for (locale, summary) in &self.summary_translations {
+            writeln!(
+                f,
+                r#"  <summary xml:lang="{}">{}</summary>"#,
+                e(locale),
+                e(summary)
+            )?;
+        }
+
+        if !self.description.is_empty() {
+            writeln!(f, "  <description>")?;
+            for paragraph in &self.description {
+                writeln!(f, "    <p>{}</p>", e(paragraph))?;
+            }
+            writeln!(f, "  </description>")?;
+        }
+
+        if let Some(developer) = &self.developer {
+            writeln!(f, "  <developer>")?;
+            writeln!(f, "    <name>{}</name>", e(developer))?;
+            writeln!(f, "  </developer>")?;
+        }
+
+        for (url_type, url) in &self.urls {
+            writeln!(f, r#"  <url type="{}">{}</url>"#, url_type.as_str(), e(url))?;
+        }
+
+        if !self.releases.is_empty() {
+            writeln!(f, "  <releases>")?;
+            for release in &self.releases {
+                let open = format!(
+                    r#"<release version="{}" date="{}""#,
+                    e(&release.version),
+                    release.date.format("%Y-%m-%d")
+                );
+                if release.description.is_none() && release.url.is_none() {
+                    writeln!(f, "    {open}/>")?;
+                    continue;
+                }
+
+                writeln!(f, "    {open}>")?;
+                if let Some(description) = &release.description {
+                    writeln!(
+                        f,
+                        "      <description>\n        <p>{}</p>\n      </description>",
+                        e(description)
+                    )?;
+                }
+                if let Some(url) = &release.url {
+                    writeln!(f, r#"      <url type="details">{}</url>"#, e(url))?;
+                }
+                writeln!(f, "    </release>")?;
+            }
+            writeln!(f, "  </releases>")?;
+        }
+
+        writeln!(f, "</component>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Extensions, Translations, Website, load_os_info};
+
+    #[test]
+    fn test_appstream_conversion() {
+        let info = load_os_info(include_str!("../../../os/aerynos.json")).unwrap();
+        let component = AppStreamComponent::from(&info);
+
+        assert_eq!(component.id, "com.aerynos.aerynos");
+        assert_eq!(component.name, "AerynOS");
+        assert_eq!(
+            component
+                .urls
+                .get(&AppStreamUrlType::Homepage)
+                .map(String::as_str),
+            Some("https://aerynos.com")
+        );
+        assert_eq!(
+            component
+                .urls
+                .get(&AppStreamUrlType::Help)
+                .map(String::as_str),
+            Some("https://aerynos.dev/")
+        );
+        assert_eq!(
+            component
+                .urls
+                .get(&AppStreamUrlType::Donation)
+                .map(String::as_str),
+            Some("https://github.com/sponsors/ikeycode")
+        );
+
+        let versions = component
+            .releases
+            .iter()
+            .map(|r| r.version.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(versions, vec!["0.2025.1", "0.24.6"]);
+    }
+
+    #[test]
+    fn test_appstream_format() {
+        let mut info = load_os_info(include_str!("../../../os/aerynos.json")).unwrap();
+        info.metadata
+            .identity
+            .translations
+            .insert("name", "de", "AerynOS für alle");
+        info.metadata
+            .identity
+            .translations
+            .insert("display", "de", "AerynOS für alle 2025");
+        let mut component = AppStreamComponent::from(&info);
+        assert!(component.to_string().contains(&format!(
+            "  <summary>{}</summary>\n  <summary xml:lang=\"de\">AerynOS für alle 2025</summary>\n",
+            info.metadata.identity.display
+        )));
+
+        component.summary = "Atomic Linux distribution".to_string();
+        component
+            .summary_translations
+            .insert("de".to_string(), "Atomare Linux-Distribution".to_string());
+        component.description = vec!["An OS with <atomic> updates & more".to_string()];
+
+        let xml = component.to_string();
+        assert!(xml.starts_with(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<component type=\"operating-system\">\n"
+        ));
+        assert!(
+            xml.contains("  <url type=\"bugtracker\">https://github.com/AerynOS/issues</url>\n")
+        );
+        assert!(xml.contains("  <name xml:lang=\"de\">AerynOS für alle</name>\n"));
+        assert!(xml.contains(
+            "  <summary>Atomic Linux distribution</summary>\n  <summary xml:lang=\"de\">Atomare Linux-Distribution</summary>\n"
+        ));
+        assert!(xml.contains("    <p>An OS with &lt;atomic&gt; updates &amp; more</p>\n"));
+        assert!(xml.contains("    <release version=\"0.2025.1\" date=\"2025-03-18\"/>\n"));
+        assert!(xml.contains(
+            "      <url type=\"details\">https://aerynos.com/blog/2025/02/14/evolve-this-os/</url>\n"
+        ));
+        assert!(xml.ends_with("</component>\n"));
+    }
+
+    #[test]
+    fn test_component_id_stable() {
+        let mut info = load_os_info(include_str!("../../../os/aerynos.json")).unwrap();
+        for (key, url) in [
+            ("zz-mirror", "https://mirror.example.com"),
+            ("alt-home", "https://aerynos.org"),
+        ] {
+            info.resources.websites.insert(
+                key.to_string(),
+                Website {
+                    url: url.parse().unwrap(),
+                    display_name: key.to_string(),
+                    scope: WebsiteScope::Home,
+                    translations: Translations::default(),
+                    extensions: Extensions::new(),
+                },
+            );
+        }
+
+        // The home page with the lowest key wins
+        assert_eq!(AppStreamComponent::from(&info).id, "org.aerynos.aerynos");
+    }
+
+    #[test]
+    fn test_component_id() {
+        assert_eq!(
+            component_id(Some("https://www.example.org/path"), "example"),
+            "org.example.example"
+        );
+        assert_eq!(component_id(None, "example"), "example");
+    }
+}
@@ -0,0 +1,230 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Login banner and message of the day rendering
+//!
+//! Renders `/etc/issue` and motd text from an [`OsInfo`] using templates with
+//! `{{placeholder}}` markers, so name, version and support links come from
+//! the document instead of being repeated by hand.
+//!
+//! `/etc/issue` is read by agetty, which expands backslash escapes such as
+//! `\r` (kernel release) and `\l` (tty). Those are passed through from the
+//! template untouched, while backslashes in document values are escaped so
+//! they print literally. Control characters are removed from document values
+//! in every format, so a document can't emit terminal escape sequences.
+
+use std::cell::Cell;
+
+use crate::page::fill_template;
+use crate::{OsInfo, WebsiteScope};
+
+/// Login banner read by getty, relative to the root
+pub const ISSUE_PATH: &str = "etc/issue";
+/// Message of the day shown after login, relative to the root
+pub const MOTD_PATH: &str = "etc/motd";
+
+/// Default `/etc/issue` layout
+pub const DEFAULT_ISSUE_TEMPLATE: &str = "{{color}}{{display}}{{reset}}
+Kernel \\r on \\m (\\l)
+
+";
+
+/// Default motd layout
+pub const DEFAULT_MOTD_TEMPLATE: &str = "Welcome to {{color}}{{display}}{{reset}}
+
+ * Website:       {{home_url}}
+ * Documentation: {{documentation_url}}
+ * Support:       {{support_url}}
+";
+
+/// Where the banner is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BannerFormat {
+    /// `/etc/issue`, using agetty's `\e` escape for colour
+    Issue,
+    /// Terminal motd, using ANSI escape sequences for colour
+    Motd,
+    /// Plain text without colour, e.g. for clients that show motd verbatim
+    Plain,
+}
+
+/// Renders a login banner or message of the day for an OS
+#[derive(Debug)]
+pub struct Banner<'a> {
+    info: &'a OsInfo,
+    format: BannerFormat,
+    template: Option<String>,
+}
+
+impl<'a> Banner<'a> {
+    /// Creates a banner using the default template for the format
+    pub fn new(info: &'a OsInfo, format: BannerFormat) -> Self {
+        Self {
+            info,
+            format,
+            template: None,
+        }
+    }
+
+    /// Overrides the banner template
+    ///
+    /// Available placeholders are `{{name}}`, `{{display}}`, `{{version}}`,
+    /// `{{version_id}}`, `{{build_id}}`, `{{codename}}`, `{{home_url}}`,
+    /// `{{documentation_url}}`, `{{support_url}}`, `{{bug_report_url}}`,
+    /// and `{{color}}` and `{{reset}}` to apply the `ansi_color` branding.
+    /// Lines using a value the document doesn't have are left out. Unknown
+    /// placeholders are kept.
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    /// Renders the banner
+    pub fn render(&self) -> String {
+        let template = self.template.as_deref().unwrap_or(match self.format {
+            BannerFormat::Issue => DEFAULT_ISSUE_TEMPLATE,
+            BannerFormat::Motd | BannerFormat::Plain => DEFAULT_MOTD_TEMPLATE,
+        });
+
+        let identity = &self.info.metadata.identity;
+        let version = &self.info.metadata.version;
+        let (color, reset) = self.color();
+        let values = [
+            ("name", Some(self.text(&identity.name))),
+            ("display", Some(self.text(&identity.display))),
+            ("version", Some(self.text(&version.full))),
+            ("version_id", Some(self.text(&version.short))),
+            ("build_id", Some(self.text(&version.build_id))),
+            (
+                "codename",
+                version.codename.as_deref().map(|c| self.text(c)),
+            ),
+            ("home_url", self.website(WebsiteScope::Home)),
+            (
+                "documentation_url",
+                self.website(WebsiteScope::Documentation),
+            ),
+            ("support_url", self.website(WebsiteScope::Support)),
+            ("bug_report_url", self.website(WebsiteScope::BugTracker)),
+            ("color", Some(color)),
+            ("reset", Some(reset)),
+        ];
+
+        let mut banner = String::new();
+        for line in template.split_inclusive('\n') {
+            let missing = Cell::new(false);
+            let line = fill_template(line, |key| {
+                let (_, value) = values.iter().find(|(name, _)| *name == key)?;
+                missing.set(missing.get() || value.is_none());
+                value.clone()
+            });
+            if !missing.get() {
+                banner.push_str(&line);
+            }
+        }
+        banner
+    }
+
+    /// Start and end sequences for the branding colour
+    fn color(&self) -> (String, String) {
+        let color = self
+            .info
+            .metadata
+            .identity
+            .ansi_color
+            .as_deref()
+            // Only SGR parameters, so a document can't inject other sequences
+            .filter(|c| !c.is_empty() && c.chars().all(|c| c.is_ascii_digit() || c == ';'));
+
+        match (color, self.format) {
+            (Some(color), BannerFormat::Issue) => (format!("\\e[{color}m"), "\\e[0m".to_string()),
+            (Some(color), BannerFormat::Motd) => (format!("\x1b[{color}m"), "\x1b[0m".to_string()),
+            _ => (String::new(), String::new()),
+        }
+    }
+
+    /// First website of a scope, in URL order
+    fn website(&self, scope: WebsiteScope) -> Option<String> {
+        self.info
+            .resources
+            .websites
+            .values()
+            .filter(|site| site.scope == scope)
+            .map(|site| site.url.to_string())
+            .min()
+            .map(|url| self.text(&url))
+    }
+
+    /// Escape a document value for the output format
+    fn text(&self, value: &str) -> String {
+        let value = value
+            .chars()
+            .filter(|c| !c.is_control())
+            .collect::<String>();
+        match self.format {
+            BannerFormat::Issue => value.replace('\\', "\\\\"),
+            BannerFormat::Motd | BannerFormat::Plain => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Nullable, load_os_info};
+
+    fn info() -> OsInfo {
+        let mut info = load_os_info(include_str!("../../../sample.json")).unwrap();
+        info.metadata.identity.ansi_color = Some("38;2;23;147;209".to_string());
+        info
+    }
+
+    #[test]
+    fn test_issue() {
+        let mut info = info();
+        info.metadata.identity.display = "AerynOS \\ 0.25.1".to_string();
+
+        assert_eq!(
+            Banner::new(&info, BannerFormat::Issue).render(),
+            "\\e[38;2;23;147;209mAerynOS \\\\ 0.25.1\\e[0m\nKernel \\r on \\m (\\l)\n\n"
+        );
+    }
+
+    #[test]
+    fn test_motd() {
+        let info = info();
+        let motd = Banner::new(&info, BannerFormat::Motd).render();
+        assert!(motd.starts_with("Welcome to \x1b[38;2;23;147;209mAerynOS 0.25.1\x1b[0m\n"));
+
+        let plain = Banner::new(&info, BannerFormat::Plain).render();
+        assert!(plain.starts_with("Welcome to AerynOS 0.25.1\n"));
+        assert!(!plain.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_missing_values() {
+        let mut info = info();
+        info.metadata.version.codename = Nullable::Absent;
+        info.metadata.identity.ansi_color = Some("1m\x1b[2J".to_string());
+
+        let banner = Banner::new(&info, BannerFormat::Motd)
+            .with_template(
+                "{{color}}{{name}}{{reset}} {{version_id}}\nCodename: {{codename}}\n{{unknown}}\n",
+            )
+            .render();
+        assert_eq!(banner, "AerynOS 0.25.1\n{{unknown}}\n");
+    }
+
+    #[test]
+    fn test_untrusted_values() {
+        let mut info = info();
+        info.metadata.identity.display = "Aeryn\x1b[2J\u{9b}31mOS\r\n".to_string();
+        info.metadata.identity.name = "{{version}}".to_string();
+
+        let motd = Banner::new(&info, BannerFormat::Motd)
+            .with_template("{{display}} {{name}}\n")
+            .render();
+        assert_eq!(motd, "Aeryn[2J31mOS {{version}}\n");
+    }
+}
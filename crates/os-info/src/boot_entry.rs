@@ -0,0 +1,206 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Boot Loader Specification entries
+//!
+//! Kernel install hooks write a Type #1 entry to `/loader/entries/` for every
+//! installed kernel, or embed an os-release in the `.osrel` section of a
+//! unified kernel image (Type #2). Deriving the title, version and sort key
+//! from the os-info document keeps boot menus consistent across releases.
+
+use std::fmt::{self, Display};
+
+use crate::probe::normalize_arch;
+use crate::{OsInfo, OsRelease};
+
+/// Directory of Type #1 entries, relative to the boot partition
+pub const BLS_ENTRIES_DIR: &str = "loader/entries";
+
+/// A Boot Loader Specification Type #1 entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootEntry {
+    /// Menu title, the OS pretty name
+    pub title: String,
+    /// Kernel version, used to order entries of the same OS
+    pub version: String,
+    /// Groups and orders entries by OS, the OS identifier
+    pub sort_key: String,
+    /// Machine the entry belongs to
+    pub machine_id: Option<String>,
+    /// EFI architecture, e.g. `x64` or `aa64`
+    pub architecture: Option<String>,
+    /// Kernel image, relative to the boot partition
+    pub linux: Option<String>,
+    /// Initrd images, relative to the boot partition
+    pub initrd: Vec<String>,
+    /// Kernel command line
+    pub options: Vec<String>,
+}
+
+impl BootEntry {
+    /// Creates an entry for a kernel of this OS
+    ///
+    /// The title and sort key match what systemd-boot derives from the
+    /// `.osrel` of a unified kernel image, so both entry types sort and
+    /// display alike. Kernel, initrd and options are left for the caller.
+    pub fn from_os_info(info: &OsInfo, kernel_version: &str) -> Self {
+        Self {
+            title: info.metadata.identity.display.clone(),
+            version: kernel_version.to_string(),
+            sort_key: info.metadata.identity.id.clone(),
+            machine_id: None,
+            architecture: efi_arch(&info.system.platform.architecture).map(str::to_string),
+            linux: None,
+            initrd: vec![],
+            options: vec![],
+        }
+    }
+
+    /// File name of the entry, `<token>-<version>.conf`
+    ///
+    /// The entry token is the machine ID when set, and the sort key
+    /// otherwise, as kernel-install does.
+    pub fn file_name(&self) -> String {
+        let token = self.machine_id.as_deref().unwrap_or(&self.sort_key);
+        format!("{token}-{}.conf", self.version)
+    }
+}
+
+/// Each key is one line, so control characters in a value are dropped rather
+/// than allowed to start another key
+impl Display for BootEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "title      {}", value(&self.title))?;
+        writeln!(f, "version    {}", value(&self.version))?;
+        writeln!(f, "sort-key   {}", value(&self.sort_key))?;
+        if let Some(machine_id) = &self.machine_id {
+            writeln!(f, "machine-id {}", value(machine_id))?;
+        }
+        if let Some(architecture) = &self.architecture {
+            writeln!(f, "architecture {}", value(architecture))?;
+        }
+        if !self.options.is_empty() {
+            writeln!(f, "options    {}", value(&self.options.join(" ")))?;
+        }
+        if let Some(linux) = &self.linux {
+            writeln!(f, "linux      {}", value(linux))?;
+        }
+        for initrd in &self.initrd {
+            writeln!(f, "initrd     {}", value(initrd))?;
+        }
+        Ok(())
+    }
+}
+
+impl OsRelease {
+    /// Creates the os-release for the `.osrel` section of a unified kernel
+    /// image
+    ///
+    /// systemd-boot shows `PRETTY_NAME` as the title and sorts by `ID` and
+    /// `VERSION_ID`, and `BUILD_ID` tells otherwise identical images apart.
+    pub fn uki(info: &OsInfo) -> Self {
+        let mut release = Self::from(info);
+        release.set("BUILD_ID", &info.metadata.version.build_id);
+        release
+    }
+}
+
+/// A value with control characters removed
+fn value(value: &str) -> String {
+    value.chars().filter(|c| !c.is_control()).collect()
+}
+
+/// Maps any known architecture name onto the EFI name used by BLS
+///
+/// Returns `None` for architectures without UEFI support.
+fn efi_arch(arch: &str) -> Option<&'static str> {
+    match normalize_arch(arch) {
+        "x86_64" => Some("x64"),
+        "x86" => Some("ia32"),
+        "aarch64" => Some("aa64"),
+        "arm" => Some("arm"),
+        "riscv64" => Some("riscv64"),
+        "loongarch64" => Some("loongarch64"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_os_info;
+
+    #[test]
+    fn test_boot_entry() {
+        let info = load_os_info(include_str!("../../../sample.json")).unwrap();
+        let mut entry = BootEntry::from_os_info(&info, "6.12.4-1.x86_64");
+        assert_eq!(entry.file_name(), "aerynos-6.12.4-1.x86_64.conf");
+
+        entry.machine_id = Some("0123456789abcdef0123456789abcdef".to_string());
+        entry.linux = Some("/0123456789abcdef0123456789abcdef/6.12.4-1.x86_64/linux".to_string());
+        entry.initrd = vec!["/0123456789abcdef0123456789abcdef/6.12.4-1.x86_64/initrd".to_string()];
+        entry.options = vec!["root=LABEL=root".to_string(), "quiet".to_string()];
+
+        assert_eq!(
+            entry.file_name(),
+            "0123456789abcdef0123456789abcdef-6.12.4-1.x86_64.conf"
+        );
+        assert_eq!(
+            entry.to_string(),
+            "title      AerynOS 0.25.1
+version    6.12.4-1.x86_64
+sort-key   aerynos
+machine-id 0123456789abcdef0123456789abcdef
+architecture x64
+options    root=LABEL=root quiet
+linux      /0123456789abcdef0123456789abcdef/6.12.4-1.x86_64/linux
+initrd     /0123456789abcdef0123456789abcdef/6.12.4-1.x86_64/initrd
+"
+        );
+    }
+
+    #[test]
+    fn test_control_characters() {
+        let mut info = load_os_info(include_str!("../../../sample.json")).unwrap();
+        info.metadata.identity.display = "AerynOS\noptions init=/bin/sh".to_string();
+        let entry = BootEntry::from_os_info(&info, "6.12.4\r\nlinux /evil");
+
+        assert_eq!(
+            entry.to_string(),
+            "title      AerynOSoptions init=/bin/sh
+version    6.12.4linux /evil
+sort-key   aerynos
+architecture x64
+"
+        );
+    }
+
+    #[test]
+    fn test_efi_architectures() {
+        for (arch, efi) in [
+            ("x86_64", Some("x64")),
+            ("amd64", Some("x64")),
+            ("i686", Some("ia32")),
+            ("arm64", Some("aa64")),
+            ("armv7hl", Some("arm")),
+            ("armv5tel", Some("arm")),
+            ("riscv64", Some("riscv64")),
+            ("ppc64le", None),
+        ] {
+            assert_eq!(efi_arch(arch), efi, "{arch}");
+        }
+    }
+
+    #[test]
+    fn test_uki_osrel() {
+        let info = load_os_info(include_str!("../../../sample.json")).unwrap();
+        let osrel = OsRelease::uki(&info).to_string();
+
+        assert!(osrel.contains("PRETTY_NAME=\"AerynOS 0.25.1\"\n"));
+        assert!(osrel.contains(&format!(
+            "BUILD_ID=\"{}\"\n",
+            info.metadata.version.build_id
+        )));
+    }
+}
@@ -0,0 +1,1316 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Builders for constructing os-info documents in code
+//!
+//! Optional fields and collections start out empty, while fields the schema
+//! requires are checked when calling `build()`.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use snafu::{OptionExt as _, ResultExt as _, Snafu, ensure};
+
+use crate::{
+    AtomicUpdates, Boot, Cadence, CadenceType, Composition, Contact, Extensions, Features,
+    Filesystem, Firmware, FormerIdentity, FundingLink, FundingPlatform, Identity, Kernel,
+    Maintainer, MaintainerRole, Metadata, OsInfo, PgpKeyError, Platform, Resources,
+    SecurityContact, SocialLink, SocialPlatform, System, Technology, Translations, Update, Url,
+    UrlError, VersionInfo, Website, WebsiteScope,
+};
+
+/// Schema version written by [`OsInfoBuilder`] unless overridden
+pub const DEFAULT_SCHEMA_VERSION: &str = "0.1";
+
+/// Errors raised when a builder is missing required data
+#[derive(Debug, Snafu)]
+pub enum BuildError {
+    #[snafu(display("missing required field `{field}`"))]
+    MissingField { field: &'static str },
+
+    #[snafu(display("required field `{field}` is empty"))]
+    EmptyField { field: &'static str },
+
+    #[snafu(display("at least one maintainer is required"))]
+    NoMaintainers,
+
+    #[snafu(display("invalid `{field}`"))]
+    InvalidField {
+        field: &'static str,
+        source: UrlError,
+    },
+
+    #[snafu(display("invalid `pgp_key`"))]
+    InvalidPgpKey { source: PgpKeyError },
+
+    #[snafu(display("`url` is not a {platform} URL: {url}"))]
+    PlatformMismatch { platform: String, url: String },
+}
+
+fn required<T>(value: Option<T>, field: &'static str) -> Result<T, BuildError> {
+    value.context(MissingFieldSnafu { field })
+}
+
+fn non_empty(value: Option<String>, field: &'static str) -> Result<String, BuildError> {
+    let value = required(value, field)?;
+    ensure!(!value.trim().is_empty(), EmptyFieldSnafu { field });
+    Ok(value)
+}
+
+fn parse_url(value: Option<String>, field: &'static str) -> Result<Option<Url>, BuildError> {
+    value
+        .map(|value| value.parse().context(InvalidFieldSnafu { field }))
+        .transpose()
+}
+
+impl OsInfo {
+    /// Returns a builder for an os-info document
+    pub fn builder() -> OsInfoBuilder {
+        OsInfoBuilder::default()
+    }
+}
+
+/// Builder for [`OsInfo`]
+#[derive(Debug, Default)]
+pub struct OsInfoBuilder {
+    version: Option<String>,
+    start_date: Option<DateTime<Utc>>,
+    metadata: Option<Metadata>,
+    system: Option<System>,
+    resources: Option<Resources>,
+    security_contact: Option<SecurityContact>,
+}
+
+impl OsInfoBuilder {
+    /// Sets the schema version, defaulting to [`DEFAULT_SCHEMA_VERSION`]
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Sets the date the OS project was started
+    pub fn start_date(mut self, start_date: DateTime<Utc>) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    /// Sets the OS metadata
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Sets the system configuration
+    pub fn system(mut self, system: System) -> Self {
+        self.system = Some(system);
+        self
+    }
+
+    /// Sets the project resources
+    pub fn resources(mut self, resources: Resources) -> Self {
+        self.resources = Some(resources);
+        self
+    }
+
+    /// Sets the security contact
+    pub fn security_contact(mut self, contact: SecurityContact) -> Self {
+        self.security_contact = Some(contact);
+        self
+    }
+
+    /// Validates and builds the document
+    pub fn build(self) -> Result<OsInfo, BuildError> {
+        Ok(OsInfo {
+            version: self
+                .version
+                .unwrap_or_else(|| DEFAULT_SCHEMA_VERSION.to_owned()),
+            start_date: required(self.start_date, "start_date")?,
+            metadata: required(self.metadata, "metadata")?,
+            system: required(self.system, "system")?,
+            resources: required(self.resources, "resources")?,
+            security_contact: self.security_contact,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl Metadata {
+    /// Returns a builder for OS metadata
+    pub fn builder() -> MetadataBuilder {
+        MetadataBuilder::default()
+    }
+}
+
+/// Builder for [`Metadata`]
+#[derive(Debug, Default)]
+pub struct MetadataBuilder {
+    identity: Option<Identity>,
+    maintainers: HashMap<String, Vec<Maintainer>>,
+    version: Option<VersionInfo>,
+}
+
+impl MetadataBuilder {
+    /// Sets the OS identity
+    pub fn identity(mut self, identity: Identity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Adds a maintainer to the given group
+    pub fn maintainer(mut self, group: impl Into<String>, maintainer: Maintainer) -> Self {
+        self.maintainers
+            .entry(group.into())
+            .or_default()
+            .push(maintainer);
+        self
+    }
+
+    /// Sets the version information
+    pub fn version(mut self, version: VersionInfo) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Validates and builds the metadata
+    pub fn build(self) -> Result<Metadata, BuildError> {
+        ensure!(!self.maintainers.is_empty(), NoMaintainersSnafu);
+
+        Ok(Metadata {
+            identity: required(self.identity, "identity")?,
+            maintainers: self.maintainers,
+            version: required(self.version, "version")?,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl Identity {
+    /// Returns a builder for an OS identity
+    pub fn builder() -> IdentityBuilder {
+        IdentityBuilder::default()
+    }
+}
+
+/// Builder for [`Identity`]
+#[derive(Debug, Default)]
+pub struct IdentityBuilder {
+    id: Option<String>,
+    id_like: Option<String>,
+    name: Option<String>,
+    display: Option<String>,
+    ansi_color: Option<String>,
+    vendor: Option<String>,
+    former_identities: Vec<FormerIdentity>,
+    translations: Translations,
+}
+
+impl IdentityBuilder {
+    /// Sets the unique identifier
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the parent OS identifier
+    pub fn id_like(mut self, id_like: impl Into<String>) -> Self {
+        self.id_like = Some(id_like.into());
+        self
+    }
+
+    /// Sets the full name
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the display name
+    pub fn display(mut self, display: impl Into<String>) -> Self {
+        self.display = Some(display.into());
+        self
+    }
+
+    /// Sets the ANSI terminal color code
+    pub fn ansi_color(mut self, ansi_color: impl Into<String>) -> Self {
+        self.ansi_color = Some(ansi_color.into());
+        self
+    }
+
+    /// Sets the vendor prefix for CPE and purl identifiers
+    pub fn vendor(mut self, vendor: impl Into<String>) -> Self {
+        self.vendor = Some(vendor.into());
+        self
+    }
+
+    /// Adds a translation of the full name
+    pub fn name_translation(mut self, locale: impl Into<String>, name: impl Into<String>) -> Self {
+        self.translations.insert("name", locale, name);
+        self
+    }
+
+    /// Adds a translation of the display name
+    pub fn display_translation(
+        mut self,
+        locale: impl Into<String>,
+        display: impl Into<String>,
+    ) -> Self {
+        self.translations.insert("display", locale, display);
+        self
+    }
+
+    /// Adds a former identity
+    pub fn former_identity(mut self, former: FormerIdentity) -> Self {
+        self.former_identities.push(former);
+        self
+    }
+
+    /// Validates and builds the identity
+    pub fn build(self) -> Result<Identity, BuildError> {
+        Ok(Identity {
+            id: non_empty(self.id, "id")?,
+            id_like: self.id_like,
+            name: non_empty(self.name, "name")?,
+            display: non_empty(self.display, "display")?,
+            ansi_color: self.ansi_color,
+            vendor: self.vendor,
+            former_identities: self.former_identities,
+            translations: self.translations,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl FormerIdentity {
+    /// Returns a builder for a former identity
+    pub fn builder() -> FormerIdentityBuilder {
+        FormerIdentityBuilder::default()
+    }
+}
+
+/// Builder for [`FormerIdentity`]
+#[derive(Debug, Default)]
+pub struct FormerIdentityBuilder {
+    id: Option<String>,
+    name: Option<String>,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    end_version: Option<String>,
+    announcement: Option<String>,
+}
+
+impl FormerIdentityBuilder {
+    /// Sets the previous identifier
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the previous name
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets when the identity started
+    pub fn start_date(mut self, start_date: DateTime<Utc>) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    /// Sets when the identity ended
+    pub fn end_date(mut self, end_date: DateTime<Utc>) -> Self {
+        self.end_date = Some(end_date);
+        self
+    }
+
+    /// Sets the version the identity changed at
+    pub fn end_version(mut self, end_version: impl Into<String>) -> Self {
+        self.end_version = Some(end_version.into());
+        self
+    }
+
+    /// Sets the announcement link
+    pub fn announcement(mut self, announcement: impl Into<String>) -> Self {
+        self.announcement = Some(announcement.into());
+        self
+    }
+
+    /// Validates and builds the former identity
+    pub fn build(self) -> Result<FormerIdentity, BuildError> {
+        Ok(FormerIdentity {
+            id: non_empty(self.id, "id")?,
+            name: non_empty(self.name, "name")?,
+            start_date: required(self.start_date, "start_date")?,
+            end_date: required(self.end_date, "end_date")?,
+            end_version: self.end_version,
+            announcement: parse_url(self.announcement, "announcement")?,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl Maintainer {
+    /// Returns a builder for a maintainer
+    pub fn builder() -> MaintainerBuilder {
+        MaintainerBuilder::default()
+    }
+}
+
+/// Builder for [`Maintainer`]
+#[derive(Debug, Default)]
+pub struct MaintainerBuilder {
+    name: Option<String>,
+    role: Option<MaintainerRole>,
+    email: Option<String>,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+}
+
+impl MaintainerBuilder {
+    /// Sets the maintainer name
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the maintainer role
+    pub fn role(mut self, role: MaintainerRole) -> Self {
+        self.role = Some(role);
+        self
+    }
+
+    /// Sets the contact email
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Sets when the maintainer joined
+    pub fn start_date(mut self, start_date: DateTime<Utc>) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    /// Sets when the maintainer left
+    pub fn end_date(mut self, end_date: DateTime<Utc>) -> Self {
+        self.end_date = Some(end_date);
+        self
+    }
+
+    /// Validates and builds the maintainer
+    pub fn build(self) -> Result<Maintainer, BuildError> {
+        Ok(Maintainer {
+            name: non_empty(self.name, "name")?,
+            role: required(self.role, "role")?,
+            email: non_empty(self.email, "email")?
+                .parse::<Contact>()
+                .context(InvalidFieldSnafu { field: "email" })?,
+            start_date: self.start_date,
+            end_date: self.end_date.into(),
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl VersionInfo {
+    /// Returns a builder for version information
+    pub fn builder() -> VersionInfoBuilder {
+        VersionInfoBuilder::default()
+    }
+}
+
+/// Builder for [`VersionInfo`]
+#[derive(Debug, Default)]
+pub struct VersionInfoBuilder {
+    full: Option<String>,
+    short: Option<String>,
+    build_id: Option<String>,
+    released: Option<DateTime<Utc>>,
+    announcement: Option<String>,
+    codename: Option<String>,
+    sysext_level: Option<String>,
+}
+
+impl VersionInfoBuilder {
+    /// Sets the complete version string
+    pub fn full(mut self, full: impl Into<String>) -> Self {
+        self.full = Some(full.into());
+        self
+    }
+
+    /// Sets the short version number
+    pub fn short(mut self, short: impl Into<String>) -> Self {
+        self.short = Some(short.into());
+        self
+    }
+
+    /// Sets the build identifier
+    pub fn build_id(mut self, build_id: impl Into<String>) -> Self {
+        self.build_id = Some(build_id.into());
+        self
+    }
+
+    /// Sets the release date
+    pub fn released(mut self, released: DateTime<Utc>) -> Self {
+        self.released = Some(released);
+        self
+    }
+
+    /// Sets the release announcement link
+    pub fn announcement(mut self, announcement: impl Into<String>) -> Self {
+        self.announcement = Some(announcement.into());
+        self
+    }
+
+    /// Sets the version codename
+    pub fn codename(mut self, codename: impl Into<String>) -> Self {
+        self.codename = Some(codename.into());
+        self
+    }
+
+    /// Sets the system extension compatibility level
+    pub fn sysext_level(mut self, sysext_level: impl Into<String>) -> Self {
+        self.sysext_level = Some(sysext_level.into());
+        self
+    }
+
+    /// Validates and builds the version information
+    pub fn build(self) -> Result<VersionInfo, BuildError> {
+        Ok(VersionInfo {
+            full: non_empty(self.full, "full")?,
+            short: non_empty(self.short, "short")?,
+            build_id: non_empty(self.build_id, "build_id")?,
+            released: required(self.released, "released")?,
+            announcement: parse_url(self.announcement, "announcement")?,
+            codename: self.codename.into(),
+            sysext_level: self.sysext_level,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl System {
+    /// Returns a builder for the system configuration
+    pub fn builder() -> SystemBuilder {
+        SystemBuilder::default()
+    }
+}
+
+/// Builder for [`System`]
+#[derive(Debug, Default)]
+pub struct SystemBuilder {
+    bases: Vec<String>,
+    core_technologies: Vec<String>,
+    optional_technologies: Vec<String>,
+    features: Option<Features>,
+    kernel_type: Option<String>,
+    kernel_name: Option<String>,
+    architecture: Option<String>,
+    variant: Option<String>,
+    update: Option<Update>,
+}
+
+impl SystemBuilder {
+    /// Adds a base system
+    pub fn base(mut self, base: impl Into<String>) -> Self {
+        self.bases.push(base.into());
+        self
+    }
+
+    /// Adds a required core technology
+    pub fn core_technology(mut self, technology: impl Into<String>) -> Self {
+        self.core_technologies.push(technology.into());
+        self
+    }
+
+    /// Adds an optional technology
+    pub fn optional_technology(mut self, technology: impl Into<String>) -> Self {
+        self.optional_technologies.push(technology.into());
+        self
+    }
+
+    /// Sets the feature configuration
+    pub fn features(mut self, features: Features) -> Self {
+        self.features = Some(features);
+        self
+    }
+
+    /// Sets the kernel type, e.g. `monolithic`
+    pub fn kernel_type(mut self, kernel_type: impl Into<String>) -> Self {
+        self.kernel_type = Some(kernel_type.into());
+        self
+    }
+
+    /// Sets the kernel name, e.g. `linux`
+    pub fn kernel_name(mut self, kernel_name: impl Into<String>) -> Self {
+        self.kernel_name = Some(kernel_name.into());
+        self
+    }
+
+    /// Sets the CPU architecture
+    pub fn architecture(mut self, architecture: impl Into<String>) -> Self {
+        self.architecture = Some(architecture.into());
+        self
+    }
+
+    /// Sets the architecture variant
+    pub fn variant(mut self, variant: impl Into<String>) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
+
+    /// Sets the update configuration
+    pub fn update(mut self, update: Update) -> Self {
+        self.update = Some(update);
+        self
+    }
+
+    /// Validates and builds the system configuration
+    pub fn build(self) -> Result<System, BuildError> {
+        Ok(System {
+            composition: Composition {
+                bases: self.bases,
+                technology: Technology {
+                    core: self.core_technologies,
+                    optional: self.optional_technologies,
+                    extensions: Extensions::new(),
+                },
+                extensions: Extensions::new(),
+            },
+            features: required(self.features, "features")?,
+            kernel: Kernel {
+                kernel_type: non_empty(self.kernel_type, "kernel_type")?,
+                name: non_empty(self.kernel_name, "kernel_name")?,
+                extensions: Extensions::new(),
+            },
+            platform: Platform {
+                architecture: non_empty(self.architecture, "architecture")?,
+                variant: non_empty(self.variant, "variant")?,
+                extensions: Extensions::new(),
+            },
+            update: required(self.update, "update")?,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl Features {
+    /// Returns a builder for the feature configuration
+    pub fn builder() -> FeaturesBuilder {
+        FeaturesBuilder::default()
+    }
+}
+
+/// Builder for [`Features`]
+#[derive(Debug, Default)]
+pub struct FeaturesBuilder {
+    update_strategy: Option<String>,
+    rollback_support: bool,
+    bootloader: Option<String>,
+    uefi: bool,
+    secure_boot: bool,
+    bios: bool,
+    default_filesystem: Option<String>,
+    supported_filesystems: Vec<String>,
+}
+
+impl FeaturesBuilder {
+    /// Sets the atomic update strategy
+    pub fn update_strategy(mut self, strategy: impl Into<String>) -> Self {
+        self.update_strategy = Some(strategy.into());
+        self
+    }
+
+    /// Sets whether updates can be rolled back
+    pub fn rollback_support(mut self, rollback_support: bool) -> Self {
+        self.rollback_support = rollback_support;
+        self
+    }
+
+    /// Sets the bootloader
+    pub fn bootloader(mut self, bootloader: impl Into<String>) -> Self {
+        self.bootloader = Some(bootloader.into());
+        self
+    }
+
+    /// Sets whether UEFI firmware is supported
+    pub fn uefi(mut self, uefi: bool) -> Self {
+        self.uefi = uefi;
+        self
+    }
+
+    /// Sets whether Secure Boot is supported
+    pub fn secure_boot(mut self, secure_boot: bool) -> Self {
+        self.secure_boot = secure_boot;
+        self
+    }
+
+    /// Sets whether legacy BIOS firmware is supported
+    pub fn bios(mut self, bios: bool) -> Self {
+        self.bios = bios;
+        self
+    }
+
+    /// Sets the default filesystem
+    pub fn default_filesystem(mut self, filesystem: impl Into<String>) -> Self {
+        self.default_filesystem = Some(filesystem.into());
+        self
+    }
+
+    /// Adds a supported filesystem
+    pub fn supported_filesystem(mut self, filesystem: impl Into<String>) -> Self {
+        self.supported_filesystems.push(filesystem.into());
+        self
+    }
+
+    /// Validates and builds the feature configuration
+    pub fn build(self) -> Result<Features, BuildError> {
+        Ok(Features {
+            atomic_updates: AtomicUpdates {
+                strategy: non_empty(self.update_strategy, "update_strategy")?,
+                rollback_support: self.rollback_support,
+                extensions: Extensions::new(),
+            },
+            boot: Boot {
+                bootloader: non_empty(self.bootloader, "bootloader")?,
+                firmware: Firmware {
+                    uefi: self.uefi,
+                    secure_boot: self.secure_boot,
+                    bios: self.bios,
+                    extensions: Extensions::new(),
+                },
+                extensions: Extensions::new(),
+            },
+            filesystem: Filesystem {
+                default: non_empty(self.default_filesystem, "default_filesystem")?,
+                supported: self.supported_filesystems,
+                extensions: Extensions::new(),
+            },
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl Update {
+    /// Returns a builder for the update configuration
+    pub fn builder() -> UpdateBuilder {
+        UpdateBuilder::default()
+    }
+}
+
+/// Builder for [`Update`]
+#[derive(Debug, Default)]
+pub struct UpdateBuilder {
+    strategy: Option<String>,
+    cadence: Option<Cadence>,
+    approach: Option<String>,
+}
+
+impl UpdateBuilder {
+    /// Sets the update strategy, e.g. `transactional`
+    pub fn strategy(mut self, strategy: impl Into<String>) -> Self {
+        self.strategy = Some(strategy.into());
+        self
+    }
+
+    /// Sets the update cadence
+    pub fn cadence(mut self, cadence: Cadence) -> Self {
+        self.cadence = Some(cadence);
+        self
+    }
+
+    /// Sets the update approach, e.g. `atomic`
+    pub fn approach(mut self, approach: impl Into<String>) -> Self {
+        self.approach = Some(approach.into());
+        self
+    }
+
+    /// Validates and builds the update configuration
+    pub fn build(self) -> Result<Update, BuildError> {
+        Ok(Update {
+            strategy: non_empty(self.strategy, "strategy")?,
+            cadence: required(self.cadence, "cadence")?,
+            approach: non_empty(self.approach, "approach")?,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl Cadence {
+    /// Returns a builder for the update cadence
+    pub fn builder() -> CadenceBuilder {
+        CadenceBuilder::default()
+    }
+}
+
+/// Builder for [`Cadence`]
+#[derive(Debug, Default)]
+pub struct CadenceBuilder {
+    cadence_type: Option<CadenceType>,
+    sync_interval: Option<String>,
+    sync_day: Option<String>,
+    release_schedule: Option<String>,
+    support_timeline: Option<String>,
+}
+
+impl CadenceBuilder {
+    /// Sets the type of release cadence
+    pub fn cadence_type(mut self, cadence_type: CadenceType) -> Self {
+        self.cadence_type = Some(cadence_type);
+        self
+    }
+
+    /// Sets how often a rolling release syncs, e.g. `weekly`
+    pub fn sync_interval(mut self, sync_interval: impl Into<String>) -> Self {
+        self.sync_interval = Some(sync_interval.into());
+        self
+    }
+
+    /// Sets the day a rolling release syncs
+    pub fn sync_day(mut self, sync_day: impl Into<String>) -> Self {
+        self.sync_day = Some(sync_day.into());
+        self
+    }
+
+    /// Sets the release schedule of fixed and point releases, e.g. `6 months`
+    pub fn release_schedule(mut self, release_schedule: impl Into<String>) -> Self {
+        self.release_schedule = Some(release_schedule.into());
+        self
+    }
+
+    /// Sets how long LTS releases are supported
+    pub fn support_timeline(mut self, support_timeline: impl Into<String>) -> Self {
+        self.support_timeline = Some(support_timeline.into());
+        self
+    }
+
+    /// Validates and builds the update cadence
+    pub fn build(self) -> Result<Cadence, BuildError> {
+        Ok(Cadence {
+            cadence_type: required(self.cadence_type, "type")?,
+            sync_interval: self.sync_interval,
+            sync_day: self.sync_day,
+            release_schedule: self.release_schedule,
+            support_timeline: self.support_timeline,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl Resources {
+    /// Returns a builder for project resources
+    pub fn builder() -> ResourcesBuilder {
+        ResourcesBuilder::default()
+    }
+}
+
+/// Builder for [`Resources`]
+#[derive(Debug, Default)]
+pub struct ResourcesBuilder {
+    resources: Resources,
+}
+
+impl ResourcesBuilder {
+    /// Adds a website under the given key
+    pub fn website(mut self, key: impl Into<String>, website: Website) -> Self {
+        self.resources.websites.insert(key.into(), website);
+        self
+    }
+
+    /// Adds a social link under the given key
+    pub fn social(mut self, key: impl Into<String>, link: SocialLink) -> Self {
+        self.resources.social.insert(key.into(), link);
+        self
+    }
+
+    /// Adds a funding link under the given key
+    pub fn funding(mut self, key: impl Into<String>, link: FundingLink) -> Self {
+        self.resources.funding.insert(key.into(), link);
+        self
+    }
+
+    /// Builds the resources
+    pub fn build(self) -> Resources {
+        self.resources
+    }
+}
+
+impl Website {
+    /// Returns a builder for a website
+    pub fn builder() -> WebsiteBuilder {
+        WebsiteBuilder::default()
+    }
+}
+
+/// Builder for [`Website`]
+#[derive(Debug, Default)]
+pub struct WebsiteBuilder {
+    url: Option<String>,
+    display_name: Option<String>,
+    scope: Option<WebsiteScope>,
+    translations: Translations,
+}
+
+impl WebsiteBuilder {
+    /// Sets the website URL
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the display name
+    pub fn display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    /// Sets the scope of the website
+    pub fn scope(mut self, scope: WebsiteScope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Adds a translation of the display name
+    pub fn display_name_translation(
+        mut self,
+        locale: impl Into<String>,
+        display_name: impl Into<String>,
+    ) -> Self {
+        self.translations
+            .insert("display_name", locale, display_name);
+        self
+    }
+
+    /// Validates and builds the website
+    pub fn build(self) -> Result<Website, BuildError> {
+        Ok(Website {
+            url: required(parse_url(self.url, "url")?, "url")?,
+            display_name: non_empty(self.display_name, "display_name")?,
+            scope: required(self.scope, "scope")?,
+            translations: self.translations,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl SocialLink {
+    /// Returns a builder for a social media link
+    pub fn builder() -> SocialLinkBuilder {
+        SocialLinkBuilder::default()
+    }
+}
+
+/// Builder for [`SocialLink`]
+#[derive(Debug, Default)]
+pub struct SocialLinkBuilder {
+    url: Option<String>,
+    display_name: Option<String>,
+    platform: Option<SocialPlatform>,
+}
+
+impl SocialLinkBuilder {
+    /// Sets the profile URL
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the display name
+    pub fn display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    /// Sets the social platform
+    pub fn platform(mut self, platform: SocialPlatform) -> Self {
+        self.platform = Some(platform);
+        self
+    }
+
+    /// Validates and builds the social link
+    ///
+    /// The URL must be a link on the platform, unless the platform is unknown.
+    pub fn build(self) -> Result<SocialLink, BuildError> {
+        let url = required(parse_url(self.url, "url")?, "url")?;
+        let platform = required(self.platform, "platform")?;
+        ensure!(
+            platform.matches_url(&url),
+            PlatformMismatchSnafu {
+                platform: platform.as_str(),
+                url: url.as_str()
+            }
+        );
+        Ok(SocialLink {
+            url,
+            display_name: non_empty(self.display_name, "display_name")?,
+            platform,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl FundingLink {
+    /// Returns a builder for a funding link
+    pub fn builder() -> FundingLinkBuilder {
+        FundingLinkBuilder::default()
+    }
+}
+
+/// Builder for [`FundingLink`]
+#[derive(Debug, Default)]
+pub struct FundingLinkBuilder {
+    url: Option<String>,
+    display_name: Option<String>,
+    platform: Option<FundingPlatform>,
+}
+
+impl FundingLinkBuilder {
+    /// Sets the funding page URL
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the display name
+    pub fn display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    /// Sets the funding platform
+    pub fn platform(mut self, platform: FundingPlatform) -> Self {
+        self.platform = Some(platform);
+        self
+    }
+
+    /// Validates and builds the funding link
+    ///
+    /// The URL must be an https funding page on the platform.
+    pub fn build(self) -> Result<FundingLink, BuildError> {
+        let url = required(parse_url(self.url, "url")?, "url")?;
+        let platform = required(self.platform, "platform")?;
+        ensure!(
+            platform.matches_url(&url),
+            PlatformMismatchSnafu {
+                platform: platform.as_str(),
+                url: url.as_str()
+            }
+        );
+        Ok(FundingLink {
+            url,
+            display_name: non_empty(self.display_name, "display_name")?,
+            platform,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl SecurityContact {
+    /// Returns a builder for the security contact
+    pub fn builder() -> SecurityContactBuilder {
+        SecurityContactBuilder::default()
+    }
+}
+
+/// Builder for [`SecurityContact`]
+#[derive(Debug, Default)]
+pub struct SecurityContactBuilder {
+    email: Option<String>,
+    pgp_key: Option<String>,
+    disclosure_policy: Option<String>,
+}
+
+impl SecurityContactBuilder {
+    /// Sets the email address for vulnerability reports
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Sets the PGP key, as an armoured block, fingerprint, key URL or WKD address
+    pub fn pgp_key(mut self, pgp_key: impl Into<String>) -> Self {
+        self.pgp_key = Some(pgp_key.into());
+        self
+    }
+
+    /// Sets the disclosure policy, as text or a URL
+    pub fn disclosure_policy(mut self, policy: impl Into<String>) -> Self {
+        self.disclosure_policy = Some(policy.into());
+        self
+    }
+
+    /// Validates and builds the security contact
+    pub fn build(self) -> Result<SecurityContact, BuildError> {
+        Ok(SecurityContact {
+            email: self
+                .email
+                .map(|email| email.parse().context(InvalidFieldSnafu { field: "email" }))
+                .transpose()?,
+            pgp_key: self
+                .pgp_key
+                .map(|key| key.parse().context(InvalidPgpKeySnafu))
+                .transpose()?,
+            disclosure_policy: self.disclosure_policy,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OsRelease;
+
+    fn date(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn system() -> System {
+        System::builder()
+            .core_technology("moss")
+            .features(
+                Features::builder()
+                    .update_strategy("immediate")
+                    .rollback_support(true)
+                    .bootloader("systemd-boot")
+                    .uefi(true)
+                    .default_filesystem("xfs")
+                    .supported_filesystem("xfs")
+                    .build()
+                    .unwrap(),
+            )
+            .kernel_type("monolithic")
+            .kernel_name("linux")
+            .architecture("x86_64")
+            .variant("desktop")
+            .update(
+                Update::builder()
+                    .strategy("transactional")
+                    .cadence(
+                        Cadence::builder()
+                            .cadence_type(CadenceType::Rolling)
+                            .sync_interval("weekly")
+                            .build()
+                            .unwrap(),
+                    )
+                    .approach("atomic")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_build_os_info() {
+        let info = OsInfo::builder()
+            .start_date(date("2023-01-01T00:00:00Z"))
+            .metadata(
+                Metadata::builder()
+                    .identity(
+                        Identity::builder()
+                            .id("derived-os")
+                            .id_like("aerynos")
+                            .name("Derived OS")
+                            .display("Derived OS 1.0")
+                            .build()
+                            .unwrap(),
+                    )
+                    .maintainer(
+                        "core",
+                        Maintainer::builder()
+                            .name("Jane Doe")
+                            .role(MaintainerRole::Founder)
+                            .email("jane@example.com")
+                            .build()
+                            .unwrap(),
+                    )
+                    .version(
+                        VersionInfo::builder()
+                            .full("1.0.0")
+                            .short("1.0")
+                            .build_id("1")
+                            .released(date("2023-02-01T00:00:00Z"))
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .system(system())
+            .resources(
+                Resources::builder()
+                    .website(
+                        "home",
+                        Website::builder()
+                            .url("https://example.com")
+                            .display_name("Home")
+                            .scope(WebsiteScope::Home)
+                            .build()
+                            .unwrap(),
+                    )
+                    .social(
+                        "matrix",
+                        SocialLink::builder()
+                            .url("https://matrix.to/#/#derived:matrix.org")
+                            .display_name("Matrix")
+                            .platform(SocialPlatform::Matrix)
+                            .build()
+                            .unwrap(),
+                    )
+                    .funding(
+                        "sponsors",
+                        FundingLink::builder()
+                            .url("https://github.com/sponsors/derived")
+                            .display_name("GitHub Sponsors")
+                            .platform(FundingPlatform::Github)
+                            .build()
+                            .unwrap(),
+                    )
+                    .build(),
+            )
+            .security_contact(
+                SecurityContact::builder()
+                    .email("security@example.com")
+                    .disclosure_policy("https://example.com/security")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(info.version, DEFAULT_SCHEMA_VERSION);
+
+        // Round-trips through the regular loader
+        let json = serde_json::to_string(&info).unwrap();
+        let parsed = crate::load_os_info(&json).unwrap();
+        assert_eq!(parsed.metadata.identity.id, "derived-os");
+
+        assert_eq!(parsed.system.platform.architecture, "x86_64");
+        assert_eq!(parsed.system.features.boot.bootloader, "systemd-boot");
+        assert_eq!(parsed.resources.funding.len(), 1);
+        assert!(matches!(
+            parsed.system.update.cadence.cadence_type,
+            CadenceType::Rolling
+        ));
+        let contact = parsed.security_contact.as_ref().unwrap();
+        assert_eq!(contact.email.as_deref(), Some("security@example.com"));
+
+        let release = OsRelease::from(&parsed);
+        assert_eq!(release.home_url.as_deref(), Some("https://example.com"));
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        assert!(matches!(
+            OsInfo::builder().build(),
+            Err(BuildError::MissingField {
+                field: "start_date"
+            })
+        ));
+        assert!(matches!(
+            OsInfo::builder()
+                .start_date(date("2023-01-01T00:00:00Z"))
+                .metadata(Metadata {
+                    identity: Identity::default(),
+                    maintainers: HashMap::new(),
+                    version: VersionInfo::builder()
+                        .full("1")
+                        .short("1")
+                        .build_id("1")
+                        .released(date("2023-01-01T00:00:00Z"))
+                        .build()
+                        .unwrap(),
+                    extensions: Extensions::new(),
+                })
+                .build(),
+            Err(BuildError::MissingField { field: "system" })
+        ));
+        assert!(matches!(
+            System::builder().features(system().features).build(),
+            Err(BuildError::MissingField {
+                field: "kernel_type"
+            })
+        ));
+        assert!(matches!(
+            Features::builder().update_strategy("immediate").build(),
+            Err(BuildError::MissingField {
+                field: "bootloader"
+            })
+        ));
+        assert!(matches!(
+            Website::builder()
+                .url("not a url")
+                .display_name("Home")
+                .build(),
+            Err(BuildError::InvalidField { field: "url", .. })
+        ));
+        assert!(matches!(
+            Identity::builder().id("x").name(" ").build(),
+            Err(BuildError::EmptyField { field: "name" })
+        ));
+        assert!(matches!(
+            Metadata::builder().build(),
+            Err(BuildError::NoMaintainers)
+        ));
+        assert!(matches!(
+            Maintainer::builder()
+                .name("A")
+                .email("a@example.com")
+                .build(),
+            Err(BuildError::MissingField { field: "role" })
+        ));
+        assert!(matches!(
+            Update::builder().strategy("transactional").build(),
+            Err(BuildError::MissingField { field: "cadence" })
+        ));
+        assert!(matches!(
+            Cadence::builder().build(),
+            Err(BuildError::MissingField { field: "type" })
+        ));
+        assert!(matches!(
+            SecurityContact::builder().email("not an email").build(),
+            Err(BuildError::InvalidField { field: "email", .. })
+        ));
+        assert!(matches!(
+            SecurityContact::builder().pgp_key("").build(),
+            Err(BuildError::InvalidPgpKey { .. })
+        ));
+    }
+
+    #[test]
+    fn test_link_platforms() {
+        assert!(matches!(
+            SocialLink::builder()
+                .url("https://example.com/aerynos")
+                .display_name("Matrix")
+                .platform(SocialPlatform::Matrix)
+                .build(),
+            Err(BuildError::PlatformMismatch { .. })
+        ));
+        assert!(
+            SocialLink::builder()
+                .url("https://forum.example.com")
+                .display_name("Forum")
+                .platform(SocialPlatform::Other("forum".to_owned()))
+                .build()
+                .is_ok()
+        );
+        assert!(matches!(
+            FundingLink::builder()
+                .url("https://example.com/donate")
+                .display_name("Ko-fi")
+                .platform(FundingPlatform::Kofi)
+                .build(),
+            Err(BuildError::PlatformMismatch { .. })
+        ));
+        assert!(matches!(
+            FundingLink::builder()
+                .url("http://ko-fi.com/derived")
+                .display_name("Ko-fi")
+                .platform(FundingPlatform::Kofi)
+                .build(),
+            Err(BuildError::PlatformMismatch { .. })
+        ));
+    }
+}
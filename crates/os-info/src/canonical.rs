@@ -0,0 +1,147 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Deterministic serialization of os-info documents
+//!
+//! The collections in the model are hash maps, so plain serialization emits
+//! keys in a different order on every run. The canonical form sorts every
+//! object key, writes timestamps as UTC RFC 3339 with a `Z` suffix and uses a
+//! fixed layout, making the output suitable for reproducible builds and
+//! content hashing.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sha2::{Digest as _, Sha256};
+use snafu::ResultExt as _;
+
+use crate::schema::{Error, JsonSerializeSnafu};
+use crate::{OsInfo, TechnologyCapabilities};
+
+/// Prefix of digests produced by [`Canonical::content_digest`]
+pub const DIGEST_PREFIX: &str = "sha256:";
+
+/// Canonical serialization for os-info and technology documents
+pub trait Canonical: Serialize {
+    /// Returns the document as a JSON value with all object keys sorted
+    fn to_canonical_value(&self) -> Result<Value, Error> {
+        serde_json::to_value(self)
+            .map(sorted)
+            .context(JsonSerializeSnafu)
+    }
+
+    /// Serializes the document as compact canonical JSON
+    ///
+    /// This is the form that digests and signatures are computed over.
+    fn to_canonical_string(&self) -> Result<String, Error> {
+        serde_json::to_string(&self.to_canonical_value()?).context(JsonSerializeSnafu)
+    }
+
+    /// Serializes the document as indented canonical JSON with a trailing newline
+    fn to_canonical_string_pretty(&self) -> Result<String, Error> {
+        let mut output = serde_json::to_string_pretty(&self.to_canonical_value()?)
+            .context(JsonSerializeSnafu)?;
+        output.push('\n');
+        Ok(output)
+    }
+
+    /// Returns the SHA-256 digest of the compact canonical form, as `sha256:<hex>`
+    fn content_digest(&self) -> Result<String, Error> {
+        let hash = Sha256::digest(self.to_canonical_string()?.as_bytes());
+        let hex = hash.iter().map(|b| format!("{b:02x}")).collect::<String>();
+        Ok(format!("{DIGEST_PREFIX}{hex}"))
+    }
+}
+
+impl Canonical for OsInfo {}
+
+impl Canonical for TechnologyCapabilities {}
+
+/// Raw documents, such as partial overlay layers
+impl Canonical for Value {}
+
+/// Recursively rebuild objects with their keys in sorted order
+///
+/// This doesn't rely on the map ordering of `serde_json`, which changes when
+/// the `preserve_order` feature is enabled anywhere in the dependency graph.
+fn sorted(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sorted(v)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sorted).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_os_info;
+
+    const SAMPLE: &str = include_str!("../../../sample.json");
+
+    #[test]
+    fn test_canonical_is_stable() {
+        let first = load_os_info(SAMPLE).unwrap();
+        let second = load_os_info(SAMPLE).unwrap();
+
+        assert_eq!(
+            first.to_canonical_string().unwrap(),
+            second.to_canonical_string().unwrap()
+        );
+        assert_eq!(
+            first.content_digest().unwrap(),
+            second.content_digest().unwrap()
+        );
+
+        let canonical = first.to_canonical_string().unwrap();
+        assert!(canonical.starts_with(r#"{"$schema":"#));
+        assert!(canonical.find(r#""metadata":"#) < canonical.find(r#""os-info-version":"#));
+        assert!(!canonical.contains('\n'));
+        assert!(first.to_canonical_string_pretty().unwrap().ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_keys_sorted() {
+        let info = load_os_info(SAMPLE).unwrap();
+        let canonical = info.to_canonical_string().unwrap();
+
+        let websites = ["\"blog\"", "\"bugs\"", "\"documentation\"", "\"homepage\""]
+            .map(|key| canonical.find(key).unwrap());
+        assert!(websites.is_sorted());
+    }
+
+    #[test]
+    fn test_timestamps_normalised() {
+        let offset = SAMPLE.replace("2025-03-18T00:00:00Z", "2025-03-18T02:00:00+02:00");
+        let a = load_os_info(SAMPLE).unwrap();
+        let b = load_os_info(&offset).unwrap();
+
+        assert!(
+            a.to_canonical_string()
+                .unwrap()
+                .contains(r#""released":"2025-03-18T00:00:00Z""#)
+        );
+        assert_eq!(a.content_digest().unwrap(), b.content_digest().unwrap());
+    }
+
+    #[test]
+    fn test_digest_changes_with_content() {
+        let a = load_os_info(SAMPLE).unwrap();
+        let mut b = load_os_info(SAMPLE).unwrap();
+        b.metadata.version.build_id = "2025.2".to_string();
+
+        let digest = a.content_digest().unwrap();
+        assert!(digest.starts_with(DIGEST_PREFIX));
+        assert_eq!(digest.len(), DIGEST_PREFIX.len() + 64);
+        assert_ne!(digest, b.content_digest().unwrap());
+    }
+}
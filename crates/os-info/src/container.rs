@@ -0,0 +1,255 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Container and chroot detection
+//!
+//! Inside a container `/usr/lib/os-info.json` describes the image rather than
+//! the machine it runs on. [`Environment`] reports which container runtime,
+//! if any, is in use, whether the process is chrooted, and where the host's
+//! root is mounted so its own document can be loaded instead.
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::os::unix::fs::MetadataExt as _;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::VENDOR_PATH;
+
+/// Marker created by Podman and Buildah, relative to the root
+pub const CONTAINERENV_PATH: &str = "run/.containerenv";
+/// Marker created by Docker, relative to the root
+pub const DOCKERENV_PATH: &str = ".dockerenv";
+/// Container manager name written by systemd, relative to the root
+pub const SYSTEMD_CONTAINER_PATH: &str = "run/systemd/container";
+/// Mount points of the host's root used by toolbox, distrobox and flatpak,
+/// relative to the root
+pub const HOST_ROOT_DIRS: &[&str] = &["run/host"];
+
+/// Container runtime, using the names of systemd's `container=` convention
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ContainerRuntime {
+    /// Docker or Moby
+    Docker,
+    /// Podman or Buildah
+    Podman,
+    /// LXC or Incus
+    Lxc,
+    /// systemd-nspawn
+    SystemdNspawn,
+    /// Flatpak or another bubblewrap sandbox
+    Flatpak,
+    /// Any other runtime, by name
+    Other(String),
+}
+
+impl ContainerRuntime {
+    /// Name as used by `systemd-detect-virt --container`
+    pub fn as_str(&self) -> &str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Lxc => "lxc",
+            ContainerRuntime::SystemdNspawn => "systemd-nspawn",
+            ContainerRuntime::Flatpak => "flatpak",
+            ContainerRuntime::Other(name) => name,
+        }
+    }
+}
+
+impl From<String> for ContainerRuntime {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "docker" | "moby" => ContainerRuntime::Docker,
+            "podman" | "oci" | "buildah" => ContainerRuntime::Podman,
+            "lxc" | "lxc-libvirt" | "incus" => ContainerRuntime::Lxc,
+            "systemd-nspawn" => ContainerRuntime::SystemdNspawn,
+            "flatpak" | "bubblewrap" => ContainerRuntime::Flatpak,
+            _ => ContainerRuntime::Other(name),
+        }
+    }
+}
+
+impl From<ContainerRuntime> for String {
+    fn from(runtime: ContainerRuntime) -> Self {
+        runtime.as_str().to_string()
+    }
+}
+
+impl Display for ContainerRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Execution environment of a filesystem root
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Environment {
+    /// Container runtime, when running in a container
+    pub container: Option<ContainerRuntime>,
+    /// Whether the root differs from the root of PID 1
+    pub chroot: bool,
+    /// Where the host's root is mounted, when it carries an os-info document
+    pub host_root: Option<PathBuf>,
+}
+
+impl Environment {
+    /// Detects the environment of the running process
+    pub fn detect() -> Self {
+        Self::detect_in("/")
+    }
+
+    /// Detects the environment from the markers below `root`
+    pub fn detect_in<P: AsRef<Path>>(root: P) -> Self {
+        let root = root.as_ref();
+
+        Self {
+            container: detect_container(root),
+            chroot: detect_chroot(root),
+            host_root: HOST_ROOT_DIRS
+                .iter()
+                .map(|dir| root.join(dir))
+                .find(|dir| dir.join(VENDOR_PATH).is_file()),
+        }
+    }
+
+    /// Whether the root is a container or chroot, so its document may not
+    /// describe the machine
+    pub fn is_isolated(&self) -> bool {
+        self.container.is_some() || self.chroot
+    }
+}
+
+/// Check the runtime markers, the systemd hand-off and PID 1's environment,
+/// then fall back to cgroup paths
+fn detect_container(root: &Path) -> Option<ContainerRuntime> {
+    if root.join(CONTAINERENV_PATH).exists() {
+        return Some(ContainerRuntime::Podman);
+    }
+    if root.join(DOCKERENV_PATH).exists() {
+        return Some(ContainerRuntime::Docker);
+    }
+
+    let systemd = fs::read_to_string(root.join(SYSTEMD_CONTAINER_PATH)).unwrap_or_default();
+    if !systemd.trim().is_empty() {
+        return Some(systemd.trim().to_string().into());
+    }
+
+    let environ = fs::read(root.join("proc/1/environ")).unwrap_or_default();
+    let name = environ
+        .split(|&b| b == 0)
+        .find_map(|var| var.strip_prefix(b"container="))
+        .filter(|name| !name.is_empty());
+    if let Some(name) = name {
+        return Some(String::from_utf8_lossy(name).into_owned().into());
+    }
+
+    let cgroup = fs::read_to_string(root.join("proc/1/cgroup")).unwrap_or_default();
+    cgroup
+        .lines()
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .find_map(|path| {
+            if path.contains("/docker/") || path.contains("/docker-") {
+                Some(ContainerRuntime::Docker)
+            } else if path.contains("/libpod-") {
+                Some(ContainerRuntime::Podman)
+            } else if path.contains("/lxc/") || path.contains("/lxc.payload") {
+                Some(ContainerRuntime::Lxc)
+            } else if path.contains("/kubepods") {
+                Some(ContainerRuntime::Other("kubernetes".to_string()))
+            } else {
+                None
+            }
+        })
+}
+
+/// Compare the root with the root of PID 1, as seen through `/proc`
+fn detect_chroot(root: &Path) -> bool {
+    let (Ok(ours), Ok(init)) = (fs::metadata(root), fs::metadata(root.join("proc/1/root"))) else {
+        return false;
+    };
+    (ours.dev(), ours.ino()) != (init.dev(), init.ino())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("os-info-container-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("proc/1")).unwrap();
+        fs::create_dir_all(root.join("run/systemd")).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_markers() {
+        let root = tree("markers");
+        assert_eq!(Environment::detect_in(&root), Environment::default());
+
+        fs::write(
+            root.join("proc/1/cgroup"),
+            "0::/system.slice/docker-0123abcd.scope\n",
+        )
+        .unwrap();
+        assert_eq!(detect_container(&root), Some(ContainerRuntime::Docker));
+
+        fs::write(
+            root.join("proc/1/environ"),
+            b"PATH=/usr/bin\0container=lxc\0HOME=/\0",
+        )
+        .unwrap();
+        assert_eq!(detect_container(&root), Some(ContainerRuntime::Lxc));
+
+        fs::write(root.join(SYSTEMD_CONTAINER_PATH), "systemd-nspawn\n").unwrap();
+        assert_eq!(
+            detect_container(&root),
+            Some(ContainerRuntime::SystemdNspawn)
+        );
+
+        fs::write(root.join(CONTAINERENV_PATH), "engine=\"podman-5.2.0\"\n").unwrap();
+        let environment = Environment::detect_in(&root);
+        assert_eq!(environment.container, Some(ContainerRuntime::Podman));
+        assert!(environment.is_isolated());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_chroot() {
+        let root = tree("chroot");
+        let outside = root.with_extension("outside");
+        fs::create_dir_all(&outside).unwrap();
+
+        std::os::unix::fs::symlink(&root, root.join("proc/1/root")).unwrap();
+        assert!(!detect_chroot(&root));
+
+        fs::remove_file(root.join("proc/1/root")).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("proc/1/root")).unwrap();
+        assert!(detect_chroot(&root));
+
+        fs::remove_dir_all(root).unwrap();
+        fs::remove_dir_all(outside).unwrap();
+    }
+
+    #[test]
+    fn test_runtime_names() {
+        assert_eq!(
+            ContainerRuntime::from("moby".to_string()),
+            ContainerRuntime::Docker
+        );
+        assert_eq!(
+            ContainerRuntime::from("wsl".to_string()),
+            ContainerRuntime::Other("wsl".to_string())
+        );
+        assert_eq!(
+            ContainerRuntime::SystemdNspawn.to_string(),
+            "systemd-nspawn"
+        );
+    }
+}
@@ -0,0 +1,518 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! systemd extension-release files
+//!
+//! System extensions (sysexts) overlay `/usr` and configuration extensions
+//! (confexts) overlay `/etc`. Each image carries an
+//! `extension-release.NAME` file that systemd-sysext matches against the
+//! host's os-release before merging it: `ID` must match, and either the
+//! `SYSEXT_LEVEL` (`CONFEXT_LEVEL`) or the `VERSION_ID` must match too.
+//!
+//! This module generates these files from an [`OsInfo`], so optional
+//! technologies can ship as extensions, and checks parsed files against a
+//! host.
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use snafu::{OptionExt as _, ResultExt as _, Snafu, ensure};
+
+use crate::os_release::{parse_assignments, shell_escape};
+use crate::probe::{normalize_arch, systemd_arch};
+use crate::{OsInfo, OsRelease, OsReleaseError};
+
+/// Extension-release directory of system extensions, relative to the image root
+pub const SYSEXT_RELEASE_DIR: &str = "usr/lib/extension-release.d";
+/// Extension-release directory of configuration extensions, relative to the image root
+pub const CONFEXT_RELEASE_DIR: &str = "etc/extension-release.d";
+/// `ID` value of extensions that apply to any OS
+pub const ANY_ID: &str = "_any";
+/// `ARCHITECTURE` value of extensions that run on any architecture
+pub const ANY_ARCHITECTURE: &str = "_any";
+
+const FILE_PREFIX: &str = "extension-release.";
+
+/// Errors raised while reading an extension-release file
+#[derive(Debug, Snafu)]
+pub enum ExtensionReleaseError {
+    #[snafu(display("failed to read {}", path.display()))]
+    ReadRelease {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("failed to parse extension-release.{name}"))]
+    ParseRelease {
+        name: String,
+        source: OsReleaseError,
+    },
+
+    #[snafu(display("{} is not named `extension-release.<name>`", path.display()))]
+    InvalidFileName { path: PathBuf },
+
+    #[snafu(display("extension-release.{name} has no `ID`"))]
+    MissingId { name: String },
+}
+
+/// Reasons an extension can't be merged on a host
+#[derive(Debug, Snafu)]
+pub enum Incompatibility {
+    #[snafu(display("extension is for `{extension}`, host is `{host}`"))]
+    IdMismatch { extension: String, host: String },
+
+    #[snafu(display("extension requires {key} `{extension}`, host has `{host}`"))]
+    LevelMismatch {
+        key: &'static str,
+        extension: String,
+        host: String,
+    },
+
+    #[snafu(display("extension requires VERSION_ID `{extension}`, host has `{host}`"))]
+    VersionMismatch { extension: String, host: String },
+
+    #[snafu(display("extension declares neither {key} nor VERSION_ID"))]
+    MissingVersion { key: &'static str },
+
+    #[snafu(display("extension is built for `{extension}`, host is `{host}`"))]
+    ArchitectureMismatch { extension: String, host: String },
+}
+
+/// Kind of extension image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionKind {
+    /// System extension, overlaying `/usr` and `/opt`
+    Sysext,
+    /// Configuration extension, overlaying `/etc`
+    Confext,
+}
+
+impl ExtensionKind {
+    /// Directory holding the extension-release file, relative to the image root
+    pub fn release_dir(&self) -> &'static str {
+        match self {
+            ExtensionKind::Sysext => SYSEXT_RELEASE_DIR,
+            ExtensionKind::Confext => CONFEXT_RELEASE_DIR,
+        }
+    }
+
+    /// Key of the compatibility level, in both host and extension files
+    pub fn level_key(&self) -> &'static str {
+        match self {
+            ExtensionKind::Sysext => "SYSEXT_LEVEL",
+            ExtensionKind::Confext => "CONFEXT_LEVEL",
+        }
+    }
+
+    /// Key of the scope list
+    pub fn scope_key(&self) -> &'static str {
+        match self {
+            ExtensionKind::Sysext => "SYSEXT_SCOPE",
+            ExtensionKind::Confext => "CONFEXT_SCOPE",
+        }
+    }
+}
+
+/// A parsed or generated `extension-release.NAME` file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionRelease {
+    /// Kind of extension
+    pub kind: ExtensionKind,
+    /// Extension name, matching the image name
+    pub name: String,
+    /// OS the extension is built for, or [`ANY_ID`]
+    pub id: String,
+    /// Host `VERSION_ID` the extension requires
+    pub version_id: Option<String>,
+    /// Host `SYSEXT_LEVEL` or `CONFEXT_LEVEL` the extension requires
+    pub level: Option<String>,
+    /// Environments the extension applies to, e.g. `system` or `portable`
+    pub scope: Vec<String>,
+    /// Architecture, using systemd names such as `x86-64`, or `_any`
+    pub architecture: Option<String>,
+    /// Additional fields
+    pub extra_fields: HashMap<String, String>,
+}
+
+impl ExtensionRelease {
+    /// Creates an extension-release with no version requirements
+    pub fn new(kind: ExtensionKind, name: impl Into<String>, id: impl Into<String>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            id: id.into(),
+            version_id: None,
+            level: None,
+            scope: vec![],
+            architecture: None,
+            extra_fields: HashMap::new(),
+        }
+    }
+
+    /// Parses the contents of `extension-release.NAME`
+    pub fn parse(
+        kind: ExtensionKind,
+        name: impl Into<String>,
+        content: &str,
+    ) -> Result<Self, ExtensionReleaseError> {
+        let name = name.into();
+        let assignments = parse_assignments(content).context(ParseReleaseSnafu { name: &name })?;
+
+        let mut id = None;
+        let mut release = Self::new(kind, &name, "");
+        for (key, value) in assignments {
+            match key.as_str() {
+                "ID" => id = Some(value),
+                "VERSION_ID" => release.version_id = Some(value),
+                "ARCHITECTURE" => release.architecture = Some(value),
+                key if key == kind.level_key() => release.level = Some(value),
+                key if key == kind.scope_key() => {
+                    release.scope = value.split_whitespace().map(str::to_string).collect();
+                }
+                _ => {
+                    release.extra_fields.insert(key, value);
+                }
+            }
+        }
+        release.id = id
+            .filter(|id| !id.is_empty())
+            .context(MissingIdSnafu { name })?;
+
+        Ok(release)
+    }
+
+    /// Reads an extension-release file, taking the name from its file name
+    pub fn from_path<P: AsRef<Path>>(
+        kind: ExtensionKind,
+        path: P,
+    ) -> Result<Self, ExtensionReleaseError> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(FILE_PREFIX))
+            .filter(|name| !name.is_empty())
+            .context(InvalidFileNameSnafu { path })?;
+        let content = fs::read_to_string(path).context(ReadReleaseSnafu { path })?;
+        Self::parse(kind, name, &content)
+    }
+
+    /// Path of the file, relative to the image root
+    pub fn path(&self) -> PathBuf {
+        Path::new(self.kind.release_dir()).join(format!("{FILE_PREFIX}{}", self.name))
+    }
+
+    /// Checks the extension against a host os-release
+    ///
+    /// Follows systemd-sysext: `ID` must match the host's `ID` or one of its
+    /// `ID_LIKE` entries. When both sides declare a level the levels must
+    /// match, otherwise the extension's `VERSION_ID` must match the host's.
+    /// Hosts without a `VERSION_ID`, such as rolling releases, accept any
+    /// extension with a matching `ID`. Extensions with `ID=_any` skip both
+    /// checks.
+    pub fn check(&self, host: &OsRelease) -> Result<(), Incompatibility> {
+        if self.id == ANY_ID {
+            return Ok(());
+        }
+
+        let id_like = host.id_like.as_deref().unwrap_or_default();
+        ensure!(
+            self.id == host.id || id_like.split_whitespace().any(|id| id == self.id),
+            IdMismatchSnafu {
+                extension: &self.id,
+                host: &host.id,
+            }
+        );
+
+        let key = self.kind.level_key();
+        if let (Some(level), Some(host_level)) = (&self.level, host.extra_fields.get(key)) {
+            ensure!(
+                level == host_level,
+                LevelMismatchSnafu {
+                    key,
+                    extension: level,
+                    host: host_level,
+                }
+            );
+            return Ok(());
+        }
+        if host.version_id.is_empty() {
+            return Ok(());
+        }
+
+        let version_id = self
+            .version_id
+            .as_ref()
+            .context(MissingVersionSnafu { key })?;
+        ensure!(
+            *version_id == host.version_id,
+            VersionMismatchSnafu {
+                extension: version_id,
+                host: &host.version_id,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Checks the extension against an os-info document, including its
+    /// architecture unless it is `ARCHITECTURE=_any`
+    pub fn check_os_info(&self, info: &OsInfo) -> Result<(), Incompatibility> {
+        self.check(&OsRelease::from(info))?;
+
+        let host = &info.system.platform.architecture;
+        if let Some(architecture) = self
+            .architecture
+            .as_ref()
+            .filter(|architecture| *architecture != ANY_ARCHITECTURE)
+        {
+            ensure!(
+                normalize_arch(architecture) == normalize_arch(host),
+                ArchitectureMismatchSnafu {
+                    extension: architecture,
+                    host,
+                }
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for ExtensionRelease {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "ID={}", shell_escape(&self.id))?;
+        if let Some(version_id) = &self.version_id {
+            writeln!(f, "VERSION_ID={}", shell_escape(version_id))?;
+        }
+        if let Some(level) = &self.level {
+            writeln!(f, "{}={}", self.kind.level_key(), shell_escape(level))?;
+        }
+        if !self.scope.is_empty() {
+            writeln!(
+                f,
+                "{}={}",
+                self.kind.scope_key(),
+                shell_escape(&self.scope.join(" "))
+            )?;
+        }
+        if let Some(architecture) = &self.architecture {
+            writeln!(f, "ARCHITECTURE={}", shell_escape(architecture))?;
+        }
+
+        let mut extra: Vec<_> = self.extra_fields.iter().collect();
+        extra.sort_by_key(|(key, _)| *key);
+        for (key, value) in extra {
+            writeln!(f, "{key}={}", shell_escape(value))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl OsInfo {
+    /// Generates the extension-release of an extension built for this OS
+    ///
+    /// System extensions are pinned to the `sysext_level` when one is
+    /// declared, so they survive updates within that level, and to the short
+    /// version otherwise. Configuration extensions are pinned to the short
+    /// version.
+    pub fn extension_release(&self, kind: ExtensionKind, name: &str) -> ExtensionRelease {
+        let version = &self.metadata.version;
+        let mut release = ExtensionRelease::new(kind, name, &self.metadata.identity.id);
+
+        match (&version.sysext_level, kind) {
+            (Some(level), ExtensionKind::Sysext) => release.level = Some(level.clone()),
+            _ => release.version_id = Some(version.short.clone()),
+        }
+        release.architecture = Some(systemd_arch(&self.system.platform.architecture).to_string());
+
+        release
+    }
+
+    /// Generates system extension-release files for the optional technologies
+    pub fn technology_extensions(&self) -> Vec<ExtensionRelease> {
+        self.system
+            .composition
+            .technology
+            .optional
+            .iter()
+            .map(|name| self.extension_release(ExtensionKind::Sysext, name))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_os_info;
+
+    fn info() -> OsInfo {
+        load_os_info(include_str!("../../../sample.json")).unwrap()
+    }
+
+    #[test]
+    fn test_generate() {
+        let info = info();
+        let extensions = info.technology_extensions();
+        let flatpak = extensions.iter().find(|e| e.name == "flatpak").unwrap();
+
+        assert_eq!(
+            flatpak.path(),
+            Path::new("usr/lib/extension-release.d/extension-release.flatpak")
+        );
+        assert_eq!(
+            flatpak.to_string(),
+            "ID=\"aerynos\"\nVERSION_ID=\"0.25.1\"\nARCHITECTURE=\"x86-64\"\n"
+        );
+        assert!(flatpak.check_os_info(&info).is_ok());
+
+        let parsed =
+            ExtensionRelease::parse(ExtensionKind::Sysext, "flatpak", &flatpak.to_string())
+                .unwrap();
+        assert_eq!(&parsed, flatpak);
+    }
+
+    #[test]
+    fn test_levels() {
+        let mut info = info();
+        info.metadata.version.sysext_level = Some("1".to_string());
+
+        let sysext = info.extension_release(ExtensionKind::Sysext, "devtools");
+        assert_eq!(sysext.level.as_deref(), Some("1"));
+        assert_eq!(sysext.version_id, None);
+
+        // A point release keeps the level, so the extension still applies
+        info.metadata.version.short = "0.25.2".to_string();
+        assert!(sysext.check_os_info(&info).is_ok());
+
+        info.metadata.version.sysext_level = Some("2".to_string());
+        assert_eq!(
+            sysext.check_os_info(&info).unwrap_err().to_string(),
+            "extension requires SYSEXT_LEVEL `1`, host has `2`"
+        );
+
+        // Confexts are pinned to the version
+        let confext = info.extension_release(ExtensionKind::Confext, "site");
+        assert_eq!(
+            confext.path(),
+            Path::new("etc/extension-release.d/extension-release.site")
+        );
+        assert_eq!(confext.version_id.as_deref(), Some("0.25.2"));
+        assert!(confext.check_os_info(&info).is_ok());
+    }
+
+    #[test]
+    fn test_check_os_release() {
+        let host = "ID=debian-derivative\nID_LIKE=debian\nVERSION_ID=12\n"
+            .parse::<OsRelease>()
+            .unwrap();
+        let check = |content: &str| {
+            ExtensionRelease::parse(ExtensionKind::Sysext, "test", content)
+                .unwrap()
+                .check(&host)
+                .map_err(|e| e.to_string())
+        };
+
+        assert!(check("ID=debian\nVERSION_ID=12").is_ok());
+        assert!(check("ID=_any").is_ok());
+        assert_eq!(
+            check("ID=fedora\nVERSION_ID=12"),
+            Err("extension is for `fedora`, host is `debian-derivative`".to_string())
+        );
+        assert_eq!(
+            check("ID=debian\nVERSION_ID=11"),
+            Err("extension requires VERSION_ID `11`, host has `12`".to_string())
+        );
+        // The host has no level, so the version is checked instead
+        assert_eq!(
+            check("ID=debian\nSYSEXT_LEVEL=1"),
+            Err("extension declares neither SYSEXT_LEVEL nor VERSION_ID".to_string())
+        );
+    }
+
+    #[test]
+    fn test_check_rolling() {
+        let host = "ID=arch\n".parse::<OsRelease>().unwrap();
+        let check = |content: &str| {
+            ExtensionRelease::parse(ExtensionKind::Sysext, "test", content)
+                .unwrap()
+                .check(&host)
+                .map_err(|e| e.to_string())
+        };
+
+        assert!(check("ID=arch").is_ok());
+        assert!(check("ID=arch\nVERSION_ID=1").is_ok());
+        assert!(check("ID=arch\nSYSEXT_LEVEL=1").is_ok());
+        assert_eq!(
+            check("ID=debian"),
+            Err("extension is for `debian`, host is `arch`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_generate_architectures() {
+        let mut info = info();
+        for (declared, systemd) in [
+            ("aarch64", "arm64"),
+            ("ppc64le", "ppc64-le"),
+            ("armv7l", "arm"),
+        ] {
+            info.system.platform.architecture = declared.to_string();
+            let release = info.extension_release(ExtensionKind::Sysext, "tools");
+            assert_eq!(release.architecture.as_deref(), Some(systemd));
+            assert!(release.check_os_info(&info).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let release = ExtensionRelease::parse(
+            ExtensionKind::Sysext,
+            "tools",
+            "ID=aerynos\nSYSEXT_LEVEL=1\nSYSEXT_SCOPE='system portable'\nARCHITECTURE=arm64\nSYSEXT_ID=tools\n",
+        )
+        .unwrap();
+        assert_eq!(release.level.as_deref(), Some("1"));
+        assert_eq!(release.scope, vec!["system", "portable"]);
+        assert_eq!(release.extra_fields.get("SYSEXT_ID").unwrap(), "tools");
+
+        let mut info = info();
+        info.metadata.version.sysext_level = Some("1".to_string());
+        assert_eq!(
+            release.check_os_info(&info).unwrap_err().to_string(),
+            "extension is built for `arm64`, host is `x86_64`"
+        );
+
+        let any = ExtensionRelease::parse(
+            ExtensionKind::Sysext,
+            "scripts",
+            "ID=aerynos\nSYSEXT_LEVEL=1\nARCHITECTURE=_any\n",
+        )
+        .unwrap();
+        assert_eq!(any.architecture.as_deref(), Some(ANY_ARCHITECTURE));
+        assert!(any.check_os_info(&info).is_ok());
+
+        assert!(matches!(
+            ExtensionRelease::parse(ExtensionKind::Sysext, "empty", "VERSION_ID=1"),
+            Err(ExtensionReleaseError::MissingId { .. })
+        ));
+
+        let dir =
+            std::env::temp_dir().join(format!("os-info-extension-release-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("extension-release.tools"), "ID=aerynos\n").unwrap();
+        let release =
+            ExtensionRelease::from_path(ExtensionKind::Sysext, dir.join("extension-release.tools"))
+                .unwrap();
+        assert_eq!(release.name, "tools");
+        assert!(matches!(
+            ExtensionRelease::from_path(ExtensionKind::Sysext, dir.join("os-release")),
+            Err(ExtensionReleaseError::InvalidFileName { .. })
+        ));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
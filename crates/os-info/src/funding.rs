@@ -0,0 +1,475 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Funding platforms and funding.json manifests
+//!
+//! This module types the platform of a [`FundingLink`] and exports the
+//! project's funding data as a [funding.json](https://fundingjson.org)
+//! manifest, listing the entity behind the OS, the OS as a project, and one
+//! channel per funding link.
+
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+use snafu::{OptionExt, Snafu, ensure};
+
+use crate::{FundingLink, MaintainerRole, OsInfo, WebsiteScope};
+
+/// Version of the funding.json specification produced
+pub const FUNDING_MANIFEST_VERSION: &str = "v1.0.0";
+
+/// Path, relative to the homepage, that lists funding manifest URLs
+pub const FUNDING_MANIFEST_WELL_KNOWN: &str = ".well-known/funding-manifest-urls";
+
+/// Errors raised while validating funding links or building a manifest
+#[derive(Debug, Snafu)]
+pub enum FundingError {
+    #[snafu(display("funding link `{key}` must use https: {url}"))]
+    InsecureUrl { key: String, url: String },
+
+    #[snafu(display("funding link `{key}` is not a {platform} URL: {url}"))]
+    PlatformMismatch {
+        key: String,
+        platform: FundingPlatform,
+        url: String,
+    },
+
+    #[snafu(display("no maintainer or security contact email for the funding entity"))]
+    NoContact,
+}
+
+/// Platform a funding link points to
+///
+/// Only the names documents are expected to use map to a variant, other
+/// spellings are kept as [`FundingPlatform::Other`] so they round-trip, and
+/// [`FundingPlatform::canonical`] resolves them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum FundingPlatform {
+    /// GitHub Sponsors
+    Github,
+    /// Ko-fi
+    Kofi,
+    /// Open Collective
+    OpenCollective,
+    /// Liberapay
+    Liberapay,
+    /// Patreon
+    Patreon,
+    /// Any other platform or spelling, by name as written
+    Other(String),
+}
+
+impl FundingPlatform {
+    /// Name used in os-info documents
+    pub fn as_str(&self) -> &str {
+        match self {
+            FundingPlatform::Github => "github",
+            FundingPlatform::Kofi => "kofi",
+            FundingPlatform::OpenCollective => "opencollective",
+            FundingPlatform::Liberapay => "liberapay",
+            FundingPlatform::Patreon => "patreon",
+            FundingPlatform::Other(name) => name,
+        }
+    }
+
+    /// The known platform this is another spelling of, such as `ko-fi`
+    pub fn canonical(&self) -> FundingPlatform {
+        match self {
+            FundingPlatform::Other(name) => match name.to_ascii_lowercase().as_str() {
+                "github" => FundingPlatform::Github,
+                "kofi" | "ko-fi" => FundingPlatform::Kofi,
+                "opencollective" | "open-collective" => FundingPlatform::OpenCollective,
+                "liberapay" => FundingPlatform::Liberapay,
+                "patreon" => FundingPlatform::Patreon,
+                _ => self.clone(),
+            },
+            _ => self.clone(),
+        }
+    }
+
+    /// Host and path prefix that links on this platform must use
+    fn url_prefix(&self) -> Option<(&'static str, &'static str)> {
+        match self.canonical() {
+            FundingPlatform::Github => Some(("github.com", "/sponsors/")),
+            FundingPlatform::Kofi => Some(("ko-fi.com", "/")),
+            FundingPlatform::OpenCollective => Some(("opencollective.com", "/")),
+            FundingPlatform::Liberapay => Some(("liberapay.com", "/")),
+            FundingPlatform::Patreon => Some(("patreon.com", "/")),
+            FundingPlatform::Other(_) => None,
+        }
+    }
+
+    /// Whether `url` is a funding page on this platform
+    ///
+    /// Unknown platforms accept any https URL.
+    pub fn matches_url(&self, url: &str) -> bool {
+        let Some(rest) = url.strip_prefix("https://") else {
+            return false;
+        };
+        let Some((expected_host, expected_path)) = self.url_prefix() else {
+            return true;
+        };
+
+        let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        host.eq_ignore_ascii_case(expected_host)
+            && path.len() > expected_path.len()
+            && path.starts_with(expected_path)
+    }
+}
+
+impl From<String> for FundingPlatform {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "github" => FundingPlatform::Github,
+            "kofi" => FundingPlatform::Kofi,
+            "opencollective" => FundingPlatform::OpenCollective,
+            "liberapay" => FundingPlatform::Liberapay,
+            "patreon" => FundingPlatform::Patreon,
+            _ => FundingPlatform::Other(name),
+        }
+    }
+}
+
+impl From<FundingPlatform> for String {
+    fn from(platform: FundingPlatform) -> Self {
+        platform.as_str().to_string()
+    }
+}
+
+impl Display for FundingPlatform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FundingLink {
+    /// Checks the link is an https URL on its declared platform
+    pub fn validate(&self, key: &str) -> Result<(), FundingError> {
+        ensure!(
+            self.url.starts_with("https://"),
+            InsecureUrlSnafu {
+                key,
+                url: &self.url
+            }
+        );
+        ensure!(
+            self.platform.matches_url(&self.url),
+            PlatformMismatchSnafu {
+                key,
+                platform: self.platform.clone(),
+                url: &self.url
+            }
+        );
+        Ok(())
+    }
+}
+
+/// A funding.json manifest
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FundingManifest {
+    /// Specification version
+    pub version: String,
+    /// Who receives the funds
+    pub entity: FundingEntity,
+    /// Projects the funds support
+    pub projects: Vec<FundingProject>,
+    /// Channels and plans
+    pub funding: Funding,
+}
+
+/// The entity receiving funding
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingEntity {
+    /// `individual`, `group`, `organisation` or `other`
+    #[serde(rename = "type")]
+    pub entity_type: String,
+    /// `owner`, `steward`, `maintainer`, `contributor` or `other`
+    pub role: String,
+    /// Entity name
+    pub name: String,
+    /// Contact email
+    pub email: String,
+    /// Short description
+    pub description: String,
+    /// Entity homepage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webpage_url: Option<FundingUrl>,
+}
+
+/// A project covered by the manifest
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingProject {
+    /// Stable project identifier
+    pub guid: String,
+    /// Project name
+    pub name: String,
+    /// Short description
+    pub description: String,
+    /// Project homepage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webpage_url: Option<FundingUrl>,
+    /// Source repository
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository_url: Option<FundingUrl>,
+    /// Topic tags
+    pub tags: Vec<String>,
+}
+
+/// A URL with an optional well-known proof location
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingUrl {
+    /// The URL
+    pub url: String,
+    /// Where the manifest URL is published under the same origin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub well_known: Option<String>,
+}
+
+/// Funding channels and plans
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Funding {
+    /// Ways to send funds
+    pub channels: Vec<FundingChannel>,
+    /// What the funds are requested for
+    pub plans: Vec<FundingPlan>,
+}
+
+/// A way to send funds
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FundingChannel {
+    /// Identifier referenced by plans, the funding link key
+    pub guid: String,
+    /// Always `payment-provider` for platform links
+    #[serde(rename = "type")]
+    pub channel_type: String,
+    /// Funding page URL
+    pub address: String,
+    /// Display name of the link
+    pub description: String,
+}
+
+/// A funding plan
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FundingPlan {
+    /// Plan identifier
+    pub guid: String,
+    /// `active` or `inactive`
+    pub status: String,
+    /// Plan name
+    pub name: String,
+    /// Plan description
+    pub description: String,
+    /// Requested amount, 0 for any amount
+    pub amount: u64,
+    /// ISO 4217 currency code
+    pub currency: String,
+    /// `one-time`, `weekly`, `fortnightly`, `monthly`, `yearly` or `other`
+    pub frequency: String,
+    /// Channel guids the plan accepts
+    pub channels: Vec<String>,
+}
+
+impl FundingManifest {
+    /// Builds a manifest from the funding links and maintainers of `info`
+    ///
+    /// Every funding link is validated against its platform first, and the
+    /// entity needs a contact email from a maintainer or the security contact.
+    pub fn from_os_info(info: &OsInfo) -> Result<Self, FundingError> {
+        let identity = &info.metadata.identity;
+
+        let mut links = info.resources.funding.iter().collect::<Vec<_>>();
+        links.sort_by_key(|(key, _)| *key);
+        for (key, link) in &links {
+            link.validate(key)?;
+        }
+
+        let channels = links
+            .into_iter()
+            .map(|(key, link)| FundingChannel {
+                guid: key.clone(),
+                channel_type: "payment-provider".to_string(),
+                address: link.url.to_string(),
+                description: link.display_name.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        let home = website(info, WebsiteScope::Home);
+        let repository = website(info, WebsiteScope::Developer);
+
+        let entity = FundingEntity {
+            entity_type: "organisation".to_string(),
+            role: "owner".to_string(),
+            name: identity.name.clone(),
+            email: contact_email(info).context(NoContactSnafu)?,
+            description: identity.display.clone(),
+            webpage_url: home.map(|url| FundingUrl {
+                well_known: Some(format!(
+                    "{}/{FUNDING_MANIFEST_WELL_KNOWN}",
+                    url.trim_end_matches('/')
+                )),
+                url: url.to_string(),
+            }),
+        };
+
+        let project = FundingProject {
+            guid: identity.id.clone(),
+            name: identity.name.clone(),
+            description: identity.display.clone(),
+            webpage_url: home.map(|url| FundingUrl {
+                url: url.to_string(),
+                well_known: None,
+            }),
+            repository_url: repository.map(|url| FundingUrl {
+                url: url.to_string(),
+                well_known: None,
+            }),
+            tags: vec!["operating-system".to_string()],
+        };
+
+        let plans = if channels.is_empty() {
+            vec![]
+        } else {
+            vec![FundingPlan {
+                guid: "donations".to_string(),
+                status: "active".to_string(),
+                name: format!("Support {}", identity.name),
+                description: format!("General support for the development of {}", identity.name),
+                amount: 0,
+                currency: "USD".to_string(),
+                frequency: "other".to_string(),
+                channels: channels.iter().map(|c| c.guid.clone()).collect(),
+            }]
+        };
+
+        Ok(Self {
+            version: FUNDING_MANIFEST_VERSION.to_string(),
+            entity,
+            projects: vec![project],
+            funding: Funding { channels, plans },
+        })
+    }
+}
+
+/// Email of the first active founder or steward, falling back to the security contact
+fn contact_email(info: &OsInfo) -> Option<String> {
+    let mut groups = info.metadata.maintainers.iter().collect::<Vec<_>>();
+    groups.sort_by_key(|(group, _)| *group);
+
+    groups
+        .into_iter()
+        .flat_map(|(_, maintainers)| maintainers)
+        .filter(|m| m.end_date.is_none())
+        .filter(|m| matches!(m.role, MaintainerRole::Founder | MaintainerRole::Steward))
+        .find_map(|m| m.email.email().map(str::to_string))
+        .or_else(|| Some(info.security_contact.as_ref()?.email.clone()?.into()))
+}
+
+/// URL of the first website with the given scope, by key
+fn website(info: &OsInfo, scope: WebsiteScope) -> Option<&str> {
+    info.resources
+        .websites
+        .iter()
+        .filter(|(_, site)| site.scope == scope)
+        .min_by_key(|(key, _)| *key)
+        .map(|(_, site)| site.url.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Extensions, load_os_info};
+    use serde_json::json;
+
+    #[test]
+    fn test_platform_urls() {
+        assert!(FundingPlatform::Github.matches_url("https://github.com/sponsors/ikeycode"));
+        assert!(!FundingPlatform::Github.matches_url("https://github.com/ikeycode"));
+        assert!(FundingPlatform::Kofi.matches_url("https://ko-fi.com/AerynOS"));
+        assert!(FundingPlatform::Patreon.matches_url("https://www.patreon.com/example"));
+        assert!(!FundingPlatform::Liberapay.matches_url("https://liberapay.com/"));
+        assert!(!FundingPlatform::OpenCollective.matches_url("http://opencollective.com/x"));
+        assert!(FundingPlatform::from("custom".to_string()).matches_url("https://example.com"));
+
+        // Other spellings are kept, but validated as the known platform
+        let kofi = FundingPlatform::from("Ko-fi".to_string());
+        assert_eq!(kofi, FundingPlatform::Other("Ko-fi".to_string()));
+        assert_eq!(kofi.canonical(), FundingPlatform::Kofi);
+        assert!(kofi.matches_url("https://ko-fi.com/AerynOS"));
+        assert!(!kofi.matches_url("https://patreon.com/AerynOS"));
+        assert_eq!(String::from(kofi), "Ko-fi");
+
+        assert_eq!(
+            String::from(FundingPlatform::OpenCollective),
+            "opencollective"
+        );
+    }
+
+    #[test]
+    fn test_validate_link() {
+        let link = FundingLink {
+            url: "https://patreon.com/example".parse().unwrap(),
+            display_name: "Patreon".to_string(),
+            platform: FundingPlatform::Kofi,
+            extensions: Extensions::new(),
+        };
+        let err = link.validate("kofi").unwrap_err();
+        assert!(matches!(err, FundingError::PlatformMismatch { .. }));
+        assert_eq!(
+            err.to_string(),
+            "funding link `kofi` is not a kofi URL: https://patreon.com/example"
+        );
+    }
+
+    #[test]
+    fn test_funding_manifest() {
+        let info = load_os_info(include_str!("../../../os/aerynos.json")).unwrap();
+        let manifest = FundingManifest::from_os_info(&info).unwrap();
+        let value = serde_json::to_value(&manifest).unwrap();
+
+        assert_eq!(value["version"], "v1.0.0");
+        assert_eq!(value["entity"]["name"], "AerynOS");
+        assert_eq!(value["entity"]["email"], "ikey@aerynos.com");
+        assert_eq!(
+            value["entity"]["webpageUrl"]["wellKnown"],
+            "https://aerynos.com/.well-known/funding-manifest-urls"
+        );
+        assert_eq!(value["projects"][0]["guid"], "aerynos");
+        assert_eq!(
+            value["funding"]["channels"],
+            json!([
+                {
+                    "guid": "github",
+                    "type": "payment-provider",
+                    "address": "https://github.com/sponsors/ikeycode",
+                    "description": "GitHub Sponsors"
+                },
+                {
+                    "guid": "kofi",
+                    "type": "payment-provider",
+                    "address": "https://ko-fi.com/AerynOS",
+                    "description": "Ko-fi"
+                }
+            ])
+        );
+        assert_eq!(
+            value["funding"]["plans"][0]["channels"],
+            json!(["github", "kofi"])
+        );
+    }
+    #[test]
+    fn test_funding_manifest_no_contact() {
+        let mut info = load_os_info(include_str!("../../../os/aerynos.json")).unwrap();
+        info.metadata.maintainers.clear();
+        info.security_contact = None;
+        assert!(matches!(
+            FundingManifest::from_os_info(&info),
+            Err(FundingError::NoContact)
+        ));
+    }
+}
@@ -0,0 +1,272 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Runtime facts about the host
+//!
+//! [`HostInfo`] is a snapshot of the machine the OS is running on, read from
+//! `/proc`, `/sys` and `/etc`. Paired with the static [`OsInfo`] in a
+//! [`HostReport`], it gives support bundles both what the OS declares and
+//! what the host actually looks like.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ContainerRuntime, Environment, OsInfo, Probe};
+
+/// Machine ID file, relative to the root
+pub const MACHINE_ID_PATH: &str = "etc/machine-id";
+/// Static hostname file, relative to the root
+pub const HOSTNAME_PATH: &str = "etc/hostname";
+/// DMI identification directory, relative to the root
+pub const DMI_DIR: &str = "sys/class/dmi/id";
+
+/// Firmware interface the host booted through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BootMode {
+    /// UEFI firmware
+    Uefi,
+    /// Legacy BIOS, or firmware without EFI runtime services
+    Bios,
+}
+
+/// Snapshot of the running host
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostInfo {
+    /// Kernel release, e.g. `6.12.4-1`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kernel_release: Option<String>,
+    /// Contents of `/etc/machine-id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub machine_id: Option<String>,
+    /// Kernel hostname, falling back to `/etc/hostname`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// How the host booted
+    pub boot_mode: BootMode,
+    /// Filesystem type of `/`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_filesystem: Option<String>,
+    /// Hypervisor, using systemd-detect-virt names such as `kvm` or `vmware`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virtualization: Option<String>,
+    /// Container runtime, such as `docker` or `systemd-nspawn`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<ContainerRuntime>,
+    /// Machine architecture, e.g. `x86_64`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub architecture: Option<String>,
+}
+
+impl HostInfo {
+    /// Collects facts about the running host
+    pub fn collect() -> Self {
+        Self::collect_from("/")
+    }
+
+    /// Collects facts from `/proc`, `/sys` and `/etc` below `root`
+    pub fn collect_from<P: AsRef<Path>>(root: P) -> Self {
+        let root = root.as_ref();
+        let probe = Probe::with_root(root);
+        let probed = probe.probe();
+
+        Self {
+            kernel_release: probed.kernel_release,
+            machine_id: read_trimmed(root.join(MACHINE_ID_PATH)),
+            hostname: probe
+                .kernel_value("hostname")
+                .or_else(|| read_trimmed(root.join(HOSTNAME_PATH))),
+            boot_mode: if probed.uefi {
+                BootMode::Uefi
+            } else {
+                BootMode::Bios
+            },
+            root_filesystem: probed.root_filesystem,
+            virtualization: detect_virtualization(root),
+            container: Environment::detect_in(root).container,
+            architecture: probed.architecture,
+        }
+    }
+}
+
+/// Static os-info and host facts, serialised together
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HostReport {
+    /// What the OS declares
+    pub os_info: OsInfo,
+    /// What the host looks like
+    pub host: HostInfo,
+}
+
+impl HostReport {
+    /// Pairs an os-info document with a host snapshot
+    pub fn new(os_info: OsInfo, host: HostInfo) -> Self {
+        Self { os_info, host }
+    }
+}
+
+fn read_trimmed(path: PathBuf) -> Option<String> {
+    let value = fs::read_to_string(path).ok()?;
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Identify the hypervisor from DMI data, then Xen and the CPU hypervisor flag
+fn detect_virtualization(root: &Path) -> Option<String> {
+    let dmi = |name: &str| read_trimmed(root.join(DMI_DIR).join(name)).unwrap_or_default();
+    let vendors = [dmi("sys_vendor"), dmi("board_vendor"), dmi("bios_vendor")];
+    let product = dmi("product_name");
+
+    const DMI_VENDORS: &[(&str, &str)] = &[
+        ("KVM", "kvm"),
+        ("Amazon EC2", "amazon"),
+        ("QEMU", "qemu"),
+        ("VMware", "vmware"),
+        ("VMW", "vmware"),
+        ("innotek GmbH", "oracle"),
+        ("VirtualBox", "oracle"),
+        ("Xen", "xen"),
+        ("Bochs", "bochs"),
+        ("Parallels", "parallels"),
+        ("BHYVE", "bhyve"),
+        ("Apple Virtualization", "apple"),
+    ];
+    for (prefix, name) in DMI_VENDORS {
+        if vendors
+            .iter()
+            .chain([&product])
+            .any(|v| v.starts_with(prefix))
+        {
+            return Some(name.to_string());
+        }
+    }
+    if vendors.iter().any(|v| v == "Microsoft Corporation") && product == "Virtual Machine" {
+        return Some("microsoft".to_string());
+    }
+    // Google also sells physical hardware, only its cloud product is a VM
+    if product == "Google Compute Engine" {
+        return Some("google".to_string());
+    }
+
+    if root.join("proc/xen").is_dir() {
+        return Some("xen".to_string());
+    }
+
+    let cpuinfo = fs::read_to_string(root.join("proc/cpuinfo")).unwrap_or_default();
+    let hypervisor = cpuinfo
+        .lines()
+        .filter(|line| line.starts_with("flags"))
+        .any(|line| line.split_whitespace().any(|flag| flag == "hypervisor"));
+    hypervisor.then(|| "vm-other".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EFI_DIR, KERNEL_SYSCTL_DIR, MOUNTS_PATH, SYSTEMD_CONTAINER_PATH, load_os_info};
+
+    fn tree(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("os-info-host-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for dir in [KERNEL_SYSCTL_DIR, DMI_DIR, "etc", "run/systemd"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join(KERNEL_SYSCTL_DIR).join("osrelease"), "6.12.4-1\n").unwrap();
+        fs::write(root.join(KERNEL_SYSCTL_DIR).join("arch"), "x86_64\n").unwrap();
+        fs::write(root.join(MOUNTS_PATH), "/dev/vda2 / xfs rw 0 0\n").unwrap();
+        fs::write(
+            root.join(MACHINE_ID_PATH),
+            "0123456789abcdef0123456789abcdef\n",
+        )
+        .unwrap();
+        fs::write(root.join(HOSTNAME_PATH), "static-name\n").unwrap();
+        root
+    }
+
+    #[test]
+    fn test_collect() {
+        let root = tree("collect");
+        fs::create_dir_all(root.join(EFI_DIR)).unwrap();
+        fs::write(root.join(KERNEL_SYSCTL_DIR).join("hostname"), "aeryn\n").unwrap();
+        fs::write(root.join(DMI_DIR).join("sys_vendor"), "QEMU\n").unwrap();
+        fs::write(root.join(SYSTEMD_CONTAINER_PATH), "systemd-nspawn\n").unwrap();
+
+        let host = HostInfo::collect_from(&root);
+        assert_eq!(
+            host,
+            HostInfo {
+                kernel_release: Some("6.12.4-1".to_string()),
+                machine_id: Some("0123456789abcdef0123456789abcdef".to_string()),
+                hostname: Some("aeryn".to_string()),
+                boot_mode: BootMode::Uefi,
+                root_filesystem: Some("xfs".to_string()),
+                virtualization: Some("qemu".to_string()),
+                container: Some(ContainerRuntime::SystemdNspawn),
+                architecture: Some("x86_64".to_string()),
+            }
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_collect_bare_metal() {
+        let root = tree("bare-metal");
+        fs::write(root.join(DMI_DIR).join("sys_vendor"), "LENOVO\n").unwrap();
+        fs::write(root.join("proc/cpuinfo"), "flags\t\t: fpu vme de pse\n").unwrap();
+
+        let host = HostInfo::collect_from(&root);
+        assert_eq!(host.hostname.as_deref(), Some("static-name"));
+        assert_eq!(host.boot_mode, BootMode::Bios);
+        assert_eq!(host.virtualization, None);
+        assert_eq!(host.container, None);
+
+        fs::write(root.join("proc/cpuinfo"), "flags\t\t: fpu vme hypervisor\n").unwrap();
+        let host = HostInfo::collect_from(&root);
+        assert_eq!(host.virtualization.as_deref(), Some("vm-other"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_google_hardware() {
+        let root = tree("google");
+        fs::write(root.join(DMI_DIR).join("sys_vendor"), "Google\n").unwrap();
+        fs::write(root.join(DMI_DIR).join("product_name"), "Eve\n").unwrap();
+        fs::write(root.join("proc/cpuinfo"), "flags\t\t: fpu vme de pse\n").unwrap();
+        assert_eq!(HostInfo::collect_from(&root).virtualization, None);
+
+        fs::write(
+            root.join(DMI_DIR).join("product_name"),
+            "Google Compute Engine\n",
+        )
+        .unwrap();
+        assert_eq!(
+            HostInfo::collect_from(&root).virtualization.as_deref(),
+            Some("google")
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        let root = tree("report");
+        let os_info = load_os_info(include_str!("../../../os/aerynos.json")).unwrap();
+        let report = HostReport::new(os_info, HostInfo::collect_from(&root));
+
+        let value = serde_json::to_value(&report).unwrap();
+        assert_eq!(value["os_info"]["metadata"]["identity"]["id"], "aerynos");
+        assert_eq!(value["host"]["boot_mode"], "bios");
+        assert_eq!(value["host"]["root_filesystem"], "xfs");
+        assert!(value["host"].get("container").is_none());
+
+        let parsed: HostReport = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.host, report.host);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
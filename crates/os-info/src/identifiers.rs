@@ -0,0 +1,312 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! CPE and purl identifiers
+//!
+//! Vulnerability databases and scanners identify operating systems by CPE 2.3
+//! names and package URLs. Both are derived from the declared
+//! [`Identity::vendor`](crate::Identity::vendor), the OS id and its short
+//! version, which is what os-release publishes as `VERSION_ID` and what
+//! scanners read back from an installed system.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+use crate::OsInfo;
+
+/// Package URL type used for the OS itself
+pub const PURL_TYPE: &str = "generic";
+
+/// A CPE 2.3 name for an operating system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cpe {
+    vendor: String,
+    product: String,
+    version: Option<String>,
+}
+
+impl Cpe {
+    /// Creates an operating system CPE
+    ///
+    /// Returns `None` if a component contains characters outside printable
+    /// ASCII, as neither CPE binding can represent them.
+    pub fn new(
+        vendor: impl Into<String>,
+        product: impl Into<String>,
+        version: Option<String>,
+    ) -> Option<Self> {
+        let cpe = Self {
+            vendor: vendor.into(),
+            product: product.into(),
+            version,
+        };
+        let printable = [&cpe.vendor, &cpe.product]
+            .into_iter()
+            .chain(&cpe.version)
+            .all(|value| value.chars().all(|c| c.is_ascii_graphic() || c == ' '));
+        printable.then_some(cpe)
+    }
+
+    /// Vendor component
+    pub fn vendor(&self) -> &str {
+        &self.vendor
+    }
+
+    /// Product component
+    pub fn product(&self) -> &str {
+        &self.product
+    }
+
+    /// Version component, any version when unset
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Formats as a CPE 2.2 URI binding, e.g. `cpe:/o:aerynos:aerynos:0.25.1`
+    ///
+    /// This is the form os-release expects in `CPE_NAME`.
+    pub fn to_uri(&self) -> String {
+        let mut uri = format!(
+            "cpe:/o:{}:{}",
+            cpe_bind(&self.vendor, Binding::Uri),
+            cpe_bind(&self.product, Binding::Uri)
+        );
+        if let Some(version) = &self.version {
+            uri.push(':');
+            uri.push_str(&cpe_bind(version, Binding::Uri));
+        }
+        uri
+    }
+}
+
+/// Formats as a CPE 2.3 formatted string, e.g. `cpe:2.3:o:aerynos:aerynos:0.25.1:*:*:*:*:*:*:*`
+impl Display for Cpe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let version = self
+            .version
+            .as_deref()
+            .map(|version| cpe_bind(version, Binding::Formatted));
+        write!(
+            f,
+            "cpe:2.3:o:{}:{}:{}:*:*:*:*:*:*:*",
+            cpe_bind(&self.vendor, Binding::Formatted),
+            cpe_bind(&self.product, Binding::Formatted),
+            version.as_deref().unwrap_or("*")
+        )
+    }
+}
+
+/// A package URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Purl {
+    /// Package type, e.g. `generic`
+    pub purl_type: String,
+    /// Namespace, usually the vendor
+    pub namespace: Option<String>,
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: Option<String>,
+    /// Qualifiers, written in key order
+    pub qualifiers: BTreeMap<String, String>,
+}
+
+impl Purl {
+    /// Creates a package URL with no qualifiers
+    pub fn new(
+        purl_type: impl Into<String>,
+        namespace: Option<String>,
+        name: impl Into<String>,
+        version: Option<String>,
+    ) -> Self {
+        Self {
+            purl_type: purl_type.into(),
+            namespace,
+            name: name.into(),
+            version,
+            qualifiers: BTreeMap::new(),
+        }
+    }
+}
+
+/// Formats as a canonical purl, e.g. `pkg:generic/aerynos/aerynos@0.25.1?distro=aerynos-0.25.1`
+impl Display for Purl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pkg:{}/", self.purl_type.to_ascii_lowercase())?;
+        if let Some(namespace) = &self.namespace {
+            for segment in namespace.split('/').filter(|s| !s.is_empty()) {
+                write!(f, "{}/", percent_encode(segment))?;
+            }
+        }
+        write!(f, "{}", percent_encode(&self.name))?;
+        if let Some(version) = &self.version {
+            write!(f, "@{}", percent_encode(version))?;
+        }
+
+        let qualifiers = self
+            .qualifiers
+            .iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(key, value)| format!("{}={}", key.to_ascii_lowercase(), percent_encode(value)))
+            .collect::<Vec<_>>();
+        if !qualifiers.is_empty() {
+            write!(f, "?{}", qualifiers.join("&"))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl OsInfo {
+    /// CPE 2.3 name of this OS, if a vendor is declared
+    pub fn cpe(&self) -> Option<Cpe> {
+        let identity = &self.metadata.identity;
+        let vendor = identity.vendor.as_ref()?;
+        Cpe::new(
+            vendor,
+            &identity.id,
+            Some(self.metadata.version.short.clone()),
+        )
+    }
+
+    /// Package URL of this OS, if a vendor is declared
+    ///
+    /// The `distro` qualifier matches the one scanners attach to packages
+    /// installed on this OS.
+    pub fn purl(&self) -> Option<Purl> {
+        let identity = &self.metadata.identity;
+        let version = &self.metadata.version;
+        let vendor = identity.vendor.as_ref()?;
+
+        let mut purl = Purl::new(
+            PURL_TYPE,
+            Some(vendor.clone()),
+            &identity.id,
+            Some(version.short.clone()),
+        );
+        purl.qualifiers.insert(
+            "distro".to_string(),
+            format!("{}-{}", identity.id, version.short),
+        );
+        Some(purl)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Binding {
+    /// CPE 2.3 formatted string
+    Formatted,
+    /// CPE 2.2 URI
+    Uri,
+}
+
+/// Bind a printable ASCII value to a CPE component
+///
+/// Values are lowercased and spaces become `_`. Letters, digits, `_`, `-` and
+/// `.` are kept as is, other characters are backslash quoted in formatted
+/// strings and percent-encoded in URIs.
+fn cpe_bind(value: &str, binding: Binding) -> String {
+    let value = value.trim();
+    if value.is_empty() {
+        return "-".to_string();
+    }
+
+    let mut bound = String::with_capacity(value.len());
+    for c in value.to_ascii_lowercase().chars() {
+        match (c, binding) {
+            (c, _) if c.is_ascii_alphanumeric() => bound.push(c),
+            ('_' | '-' | '.', _) => bound.push(c),
+            (' ', _) => bound.push('_'),
+            (c, Binding::Formatted) => {
+                bound.push('\\');
+                bound.push(c);
+            }
+            (c, Binding::Uri) => bound.push_str(&format!("%{:02x}", c as u32)),
+        }
+    }
+    bound
+}
+
+/// Percent-encode everything outside the RFC 3986 unreserved set
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_os_info;
+
+    #[test]
+    fn test_os_identifiers() {
+        let mut info = load_os_info(include_str!("../../../os/aerynos.json")).unwrap();
+
+        assert_eq!(
+            info.cpe().unwrap().to_string(),
+            "cpe:2.3:o:aerynos:aerynos:0.25.1:*:*:*:*:*:*:*"
+        );
+        assert_eq!(
+            info.cpe().unwrap().to_uri(),
+            "cpe:/o:aerynos:aerynos:0.25.1"
+        );
+        assert_eq!(
+            info.purl().unwrap().to_string(),
+            "pkg:generic/aerynos/aerynos@0.25.1?distro=aerynos-0.25.1"
+        );
+
+        // Neither binding can carry non-ASCII text
+        info.metadata.identity.vendor = Some("Müller".to_string());
+        assert!(info.cpe().is_none());
+
+        info.metadata.identity.vendor = None;
+        assert!(info.cpe().is_none());
+        assert!(info.purl().is_none());
+    }
+
+    #[test]
+    fn test_cpe_bind() {
+        let formatted = |value| cpe_bind(value, Binding::Formatted);
+        assert_eq!(formatted("Example Corp"), "example_corp");
+        assert_eq!(formatted("1.0:beta*"), "1.0\\:beta\\*");
+        assert_eq!(formatted("a_b-c.d"), "a_b-c.d");
+        assert_eq!(formatted(""), "-");
+
+        let uri = |value| cpe_bind(value, Binding::Uri);
+        assert_eq!(uri("Example Corp"), "example_corp");
+        assert_eq!(uri("1.0:beta*"), "1.0%3abeta%2a");
+        assert_eq!(uri(""), "-");
+
+        let cpe = Cpe::new("ACME", "os", None).unwrap();
+        assert_eq!(cpe.to_string(), "cpe:2.3:o:acme:os:*:*:*:*:*:*:*:*");
+        assert_eq!(cpe.to_uri(), "cpe:/o:acme:os");
+        assert!(Cpe::new("acme", "os", Some("1.0\u{e9}".to_string())).is_none());
+    }
+
+    #[test]
+    fn test_purl_encoding() {
+        let mut purl = Purl::new(
+            "Generic",
+            Some("acme/labs".to_string()),
+            "my os",
+            Some("1.0+rc1".to_string()),
+        );
+        purl.qualifiers
+            .insert("arch".to_string(), "x86_64".to_string());
+        purl.qualifiers.insert("empty".to_string(), String::new());
+
+        assert_eq!(
+            purl.to_string(),
+            "pkg:generic/acme/labs/my%20os@1.0%2Brc1?arch=x86_64"
+        );
+    }
+}
@@ -9,7 +9,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 mod schema;
 pub use schema::*;
@@ -17,6 +17,82 @@ mod technology;
 pub use technology::*;
 mod os_release;
 pub use os_release::*;
+mod loader;
+pub use loader::*;
+mod builder;
+pub use builder::*;
+mod canonical;
+pub use canonical::*;
+mod signature;
+pub use signature::*;
+mod security_txt;
+pub use security_txt::*;
+mod pgp;
+pub use pgp::*;
+mod page;
+pub use page::*;
+mod appstream;
+pub use appstream::*;
+mod sbom;
+pub use sbom::*;
+mod identifiers;
+pub use identifiers::*;
+mod funding;
+pub use funding::*;
+mod social;
+pub use social::*;
+mod url;
+pub use url::*;
+mod locale;
+pub use locale::*;
+mod probe;
+pub use probe::*;
+mod host;
+pub use host::*;
+mod container;
+pub use container::*;
+mod extension_release;
+pub use extension_release::*;
+mod lsb_release;
+pub use lsb_release::*;
+mod banner;
+pub use banner::*;
+mod boot_entry;
+pub use boot_entry::*;
+mod nullable;
+pub use nullable::*;
+mod validation;
+pub use validation::*;
+
+/// Fields not described by the schema, keyed by their JSON name
+///
+/// Vendor-specific data should use keys of the form `x-<vendor>-<name>`, see
+/// [`vendor_extension_key`].
+pub type Extensions = BTreeMap<String, serde_json::Value>;
+
+/// Prefix reserved for vendor extension fields
+pub const VENDOR_EXTENSION_PREFIX: &str = "x-";
+
+/// Builds a namespaced vendor extension key, e.g. `x-aerynos-installer`
+pub fn vendor_extension_key(vendor: &str, name: &str) -> String {
+    format!("{VENDOR_EXTENSION_PREFIX}{vendor}-{name}")
+}
+
+/// Returns true if the key follows the vendor extension naming convention
+pub fn is_vendor_extension_key(key: &str) -> bool {
+    let Some((vendor, name)) = key
+        .strip_prefix(VENDOR_EXTENSION_PREFIX)
+        .and_then(|rest| rest.split_once('-'))
+    else {
+        return false;
+    };
+    let valid = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    };
+    valid(vendor) && valid(name)
+}
 
 /// Top-level structure containing all OS information
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,8 +109,49 @@ pub struct OsInfo {
     /// Links to project resources
     pub resources: Resources,
     /// Security contact information
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub security_contact: Option<SecurityContact>,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl OsInfo {
+    /// Looks up a field by JSON pointer or dotted path, see [`json_pointer`]
+    ///
+    /// The document is serialised for every lookup, callers resolving many
+    /// fields should serialise it once and use [`json_pointer`] instead.
+    pub fn get(&self, path: &str) -> Option<serde_json::Value> {
+        let mut document = serde_json::to_value(self).ok()?;
+        document
+            .pointer_mut(&json_pointer(path))
+            .map(serde_json::Value::take)
+    }
+}
+
+/// Turns a lookup path into a JSON pointer
+///
+/// Paths starting with `/` are already JSON pointers, anything else is split
+/// on `.`, e.g. `metadata.identity.name` becomes `/metadata/identity/name`.
+pub fn json_pointer(path: &str) -> String {
+    if path.is_empty() || path.starts_with('/') {
+        return path.to_string();
+    }
+    path.split('.')
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Formats an error followed by each of its sources, separated by `: `
+pub fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+    }
+    message
 }
 
 /// Metadata about the OS including identity, maintainers and version information
@@ -46,6 +163,9 @@ pub struct Metadata {
     pub maintainers: HashMap<String, Vec<Maintainer>>,
     /// Version information
     pub version: VersionInfo,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Identity information for the OS
@@ -55,16 +175,26 @@ pub struct Identity {
     /// Unique identifier for the OS
     pub id: String,
     /// Parent OS this is based on/similar to
-    #[serde(rename = "id_like")]
+    #[serde(rename = "id_like", skip_serializing_if = "Option::is_none")]
     pub id_like: Option<String>,
     /// Full name of the OS
     pub name: String,
     /// Display name/branding
     pub display: String,
     /// ANSI terminal color code for branding
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ansi_color: Option<String>,
+    /// Vendor prefix used for CPE and purl identifiers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<String>,
     /// Previous identities/names
     pub former_identities: Vec<FormerIdentity>,
+    /// Translations of `name` and `display`
+    #[serde(flatten)]
+    pub translations: Translations,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten, deserialize_with = "locale::extensions_without_translations")]
+    pub extensions: Extensions,
 }
 
 /// Historical identity information
@@ -79,9 +209,14 @@ pub struct FormerIdentity {
     /// When this identity ended
     pub end_date: DateTime<Utc>,
     /// Version when identity was changed
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub end_version: Option<String>,
     /// Link to announcement of change
-    pub announcement: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announcement: Option<Url>,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Information about a project maintainer
@@ -91,12 +226,17 @@ pub struct Maintainer {
     pub name: String,
     /// Role/position in project
     pub role: MaintainerRole,
-    /// Contact email
-    pub email: String,
+    /// Contact email address or team page
+    pub email: Contact,
     /// When the maintainer joined the project
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub start_date: Option<DateTime<Utc>>,
     /// When the maintainer left the project (if applicable)
-    pub end_date: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Nullable::is_absent")]
+    pub end_date: Nullable<DateTime<Utc>>,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Role types for maintainers
@@ -125,13 +265,22 @@ pub struct VersionInfo {
     /// Release date
     pub released: DateTime<Utc>,
     /// Link to release announcement
-    pub announcement: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announcement: Option<Url>,
     /// Version codename
-    pub codename: Option<String>,
+    #[serde(default, skip_serializing_if = "Nullable::is_absent")]
+    pub codename: Nullable<String>,
+    /// Compatibility level for system extensions, kept across releases that
+    /// don't break the extension ABI
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sysext_level: Option<String>,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Core system information and configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct System {
     /// System composition details
     pub composition: Composition,
@@ -143,6 +292,9 @@ pub struct System {
     pub platform: Platform,
     /// Update configuration
     pub update: Update,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// System composition including base systems and technologies
@@ -153,6 +305,9 @@ pub struct Composition {
     pub bases: Vec<String>,
     /// Technology components
     pub technology: Technology,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Core and optional technology components
@@ -162,10 +317,13 @@ pub struct Technology {
     pub core: Vec<String>,
     /// Optional add-on technologies
     pub optional: Vec<String>,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// System feature configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Features {
     /// Atomic update settings
     pub atomic_updates: AtomicUpdates,
@@ -173,28 +331,37 @@ pub struct Features {
     pub boot: Boot,
     /// Filesystem settings
     pub filesystem: Filesystem,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Atomic update system configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct AtomicUpdates {
     /// Update strategy used
     pub strategy: String,
     /// Whether rollbacks are supported
     pub rollback_support: bool,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Boot configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Boot {
     /// Bootloader used
     pub bootloader: String,
     /// Firmware support details
     pub firmware: Firmware,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Firmware support configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Firmware {
     /// UEFI support
     pub uefi: bool,
@@ -202,38 +369,50 @@ pub struct Firmware {
     pub secure_boot: bool,
     /// Legacy BIOS support
     pub bios: bool,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Filesystem configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Filesystem {
     /// Default filesystem
     pub default: String,
     /// Supported filesystems
     pub supported: Vec<String>,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Kernel information
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Kernel {
     /// Type of kernel
     #[serde(rename = "type")]
     pub kernel_type: String,
     /// Kernel name
     pub name: String,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Platform architecture information
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Platform {
     /// CPU architecture
     pub architecture: String,
     /// Architecture variant
     pub variant: String,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Update strategy configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Update {
     /// Update strategy used
     pub strategy: String,
@@ -241,29 +420,40 @@ pub struct Update {
     pub cadence: Cadence,
     /// Update approach used
     pub approach: String,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Update cadence configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Cadence {
     /// Type of update cadence
     #[serde(rename = "type")]
     pub cadence_type: CadenceType,
     /// Update sync interval
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sync_interval: Option<String>,
     /// Day updates sync
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sync_day: Option<String>,
     /// Release schedule
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub release_schedule: Option<String>,
     /// Support timeline
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub support_timeline: Option<String>,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Types of update cadence
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CadenceType {
     /// Rolling release
+    #[default]
     Rolling,
     /// Fixed point release
     Fixed,
@@ -274,7 +464,7 @@ pub enum CadenceType {
 }
 
 /// Project resources like websites and social media
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Resources {
     /// Project websites
     pub websites: HashMap<String, Website>,
@@ -282,21 +472,30 @@ pub struct Resources {
     pub social: HashMap<String, SocialLink>,
     /// Funding platform links
     pub funding: HashMap<String, FundingLink>,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Website information
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Website {
     /// Website URL
-    pub url: String,
+    pub url: Url,
     /// Display name
     pub display_name: String,
     /// Website scope/purpose
     pub scope: WebsiteScope,
+    /// Translations of `display_name`
+    #[serde(flatten)]
+    pub translations: Translations,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten, deserialize_with = "locale::extensions_without_translations")]
+    pub extensions: Extensions,
 }
 
 /// Website scope/purpose
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash, Ord, PartialOrd)]
 #[serde(rename_all = "kebab-case")]
 pub enum WebsiteScope {
     /// Main project homepage
@@ -329,32 +528,44 @@ pub enum WebsiteScope {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SocialLink {
     /// Social media URL
-    pub url: String,
+    pub url: Url,
     /// Display name
     pub display_name: String,
-    /// Platform name
-    pub platform: String,
+    /// Social platform
+    pub platform: SocialPlatform,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// Funding platform link
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FundingLink {
     /// Funding page URL
-    pub url: String,
+    pub url: Url,
     /// Display name
     pub display_name: String,
-    /// Platform name
-    pub platform: String,
+    /// Funding platform
+    pub platform: FundingPlatform,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 /// Security contact information for vulnerability reporting
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SecurityContact {
     /// Email address for security-related communications
-    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<Email>,
     /// PGP key for encrypted vulnerability reports
-    pub pgp_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pgp_key: Option<PgpKey>,
     /// Vulnerability disclosure policy
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub disclosure_policy: Option<String>,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 #[cfg(test)]
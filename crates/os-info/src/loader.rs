@@ -0,0 +1,645 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Layered loading of os-info documents
+//!
+//! The vendor document in `/usr/lib/os-info.json` is the base layer. Without
+//! it, the base OS definition `/usr/lib/os-info/os/{id}.json` for the `ID` in
+//! os-release is used instead. Local overrides are applied on top of it as
+//! JSON merge patches (RFC 7396), first `/etc/os-info.json` and then every
+//! `/etc/os-info.d/*.json` drop-in in lexical order. A complete document is
+//! itself a valid merge patch, so a full copy in `/etc` keeps working as
+//! before.
+//!
+//! In [verified](Loader::verified) mode every layer must carry a detached
+//! signature from a vendor key. Keys added by the administrator in `/etc` are
+//! only trusted after [`Loader::trust_local_keys`].
+//!
+//! Inside a container the resolved document describes the image. The
+//! [environment](LoadedOsInfo::environment) of the root is reported with
+//! every load, and [`Loader::host`] switches to the host's document when its
+//! root is mounted into the container.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+use snafu::{ResultExt as _, ensure};
+
+use crate::schema::{
+    Error, NoDocumentSnafu, ParseLayerSnafu, ReadLayerSnafu, ResolveDocumentSnafu,
+    TrustedKeysSnafu, VerifySnafu,
+};
+use crate::{
+    Environment, KeyId, Keyring, OS_RELEASE_PATH, OsInfo, OsRelease, Signature,
+    VENDOR_OS_RELEASE_PATH, json_pointer,
+};
+
+/// Vendor-provided default document, relative to the root
+pub const VENDOR_PATH: &str = "usr/lib/os-info.json";
+/// Directory of base OS definitions, relative to the root
+pub const OS_DEFINITIONS_DIR: &str = "usr/lib/os-info/os";
+/// Local override document, relative to the root
+pub const LOCAL_PATH: &str = "etc/os-info.json";
+/// Directory of local drop-in overrides, relative to the root
+pub const DROP_IN_DIR: &str = "etc/os-info.d";
+
+/// The kind of layer a value was resolved from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerKind {
+    /// Vendor default in `/usr/lib/os-info.json`, or the base OS definition
+    Vendor,
+    /// Local override in `/etc/os-info.json`
+    Local,
+    /// Drop-in override in `/etc/os-info.d/`
+    DropIn,
+}
+
+/// A single document that contributed to the resolved os-info
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layer {
+    /// Kind of layer
+    pub kind: LayerKind,
+    /// Path the layer was read from
+    pub path: PathBuf,
+    /// Key that signed the layer, when loaded in verified mode
+    pub signed_by: Option<KeyId>,
+}
+
+/// Loads os-info documents from a filesystem root, applying local overlays
+#[derive(Debug, Clone)]
+pub struct Loader {
+    root: PathBuf,
+    verification: Verification,
+}
+
+/// How layer signatures are checked
+#[derive(Debug, Clone)]
+enum Verification {
+    None,
+    TrustedKeys { local: bool },
+    Keyring(Keyring),
+}
+
+impl Default for Loader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Loader {
+    /// Creates a loader for the running system
+    pub fn new() -> Self {
+        Self::with_root("/")
+    }
+
+    /// Creates a loader that resolves all paths below `root`
+    pub fn with_root<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            verification: Verification::None,
+        }
+    }
+
+    /// Requires every layer to be signed by a key from the vendor trusted key
+    /// directory below the root
+    pub fn verified(mut self) -> Self {
+        self.verification = Verification::TrustedKeys { local: false };
+        self
+    }
+
+    /// Like [`Loader::verified`], but also trusts the keys in
+    /// [`LOCAL_KEYS_DIR`](crate::LOCAL_KEYS_DIR)
+    ///
+    /// Anyone who can add a local key can sign their own overrides, so layers
+    /// are then only as trustworthy as `/etc`.
+    pub fn trust_local_keys(mut self) -> Self {
+        self.verification = Verification::TrustedKeys { local: true };
+        self
+    }
+
+    /// Requires every layer to be signed by a key from the given keyring
+    pub fn verified_with(mut self, keyring: Keyring) -> Self {
+        self.verification = Verification::Keyring(keyring);
+        self
+    }
+
+    /// Filesystem root used by this loader
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Detects whether the root is a container or chroot
+    pub fn environment(&self) -> Environment {
+        Environment::detect_in(&self.root)
+    }
+
+    /// Creates a loader for the host's document, with the same verification
+    ///
+    /// Returns `None` unless the root is a container or chroot with the
+    /// host's root mounted into it, e.g. at `/run/host`.
+    pub fn host(&self) -> Option<Self> {
+        let environment = self.environment();
+        if !environment.is_isolated() {
+            return None;
+        }
+        Some(Self {
+            root: environment.host_root?,
+            verification: self.verification.clone(),
+        })
+    }
+
+    /// Returns the existing layers in the order they are applied
+    pub fn layers(&self) -> Result<Vec<Layer>, Error> {
+        let mut layers = vec![];
+
+        // The base OS definition is only used when there is no vendor document
+        let vendor = Some(self.root.join(VENDOR_PATH))
+            .filter(|path| path.is_file())
+            .or_else(|| self.os_definition().filter(|path| path.is_file()));
+        let local = Some(self.root.join(LOCAL_PATH)).filter(|path| path.is_file());
+
+        for (kind, path) in [(LayerKind::Vendor, vendor), (LayerKind::Local, local)] {
+            if let Some(path) = path {
+                layers.push(Layer {
+                    kind,
+                    path,
+                    signed_by: None,
+                });
+            }
+        }
+
+        let dir = self.root.join(DROP_IN_DIR);
+        if dir.is_dir() {
+            let mut drop_ins = fs::read_dir(&dir)
+                .context(ReadLayerSnafu { path: &dir })?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<Result<Vec<_>, _>>()
+                .context(ReadLayerSnafu { path: &dir })?;
+            drop_ins.retain(|p| p.is_file() && p.extension().is_some_and(|e| e == "json"));
+            drop_ins.sort();
+            layers.extend(drop_ins.into_iter().map(|path| Layer {
+                kind: LayerKind::DropIn,
+                path,
+                signed_by: None,
+            }));
+        }
+
+        Ok(layers)
+    }
+
+    /// Base OS definition for the `ID` in the root's os-release
+    fn os_definition(&self) -> Option<PathBuf> {
+        let release = [OS_RELEASE_PATH, VENDOR_OS_RELEASE_PATH]
+            .iter()
+            .find_map(|path| fs::read_to_string(self.root.join(path)).ok())?
+            .parse::<OsRelease>()
+            .ok()?;
+        // The ID is a path component, so it must not escape the directory
+        let valid = !release.id.is_empty()
+            && release
+                .id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+            && !release.id.starts_with('.');
+        valid.then(|| {
+            self.root
+                .join(OS_DEFINITIONS_DIR)
+                .join(format!("{}.json", release.id))
+        })
+    }
+
+    /// Resolves all layers into a single os-info document
+    pub fn load(&self) -> Result<LoadedOsInfo, Error> {
+        let mut layers = self.layers()?;
+        ensure!(!layers.is_empty(), NoDocumentSnafu { root: &self.root });
+
+        let keyring = match &self.verification {
+            Verification::None => None,
+            Verification::TrustedKeys { local: false } => {
+                Some(Keyring::load_trusted(&self.root).context(TrustedKeysSnafu)?)
+            }
+            Verification::TrustedKeys { local: true } => {
+                Some(Keyring::load_trusted_with_local(&self.root).context(TrustedKeysSnafu)?)
+            }
+            Verification::Keyring(keyring) => Some(keyring.clone()),
+        };
+
+        let mut document = Value::Null;
+        let mut provenance = BTreeMap::new();
+
+        for (index, layer) in layers.iter_mut().enumerate() {
+            let content =
+                fs::read_to_string(&layer.path).context(ReadLayerSnafu { path: &layer.path })?;
+            let patch =
+                serde_json::from_str(&content).context(ParseLayerSnafu { path: &layer.path })?;
+            if let Some(keyring) = &keyring {
+                layer.signed_by = Some(verify_layer(keyring, &layer.path, &patch)?);
+            }
+            merge_patch(&mut document, patch, "", index, &mut provenance);
+        }
+
+        let info = serde_json::from_value(document).context(ResolveDocumentSnafu {
+            layers: layers.iter().map(|l| l.path.clone()).collect::<Vec<_>>(),
+        })?;
+
+        Ok(LoadedOsInfo {
+            info,
+            layers,
+            environment: self.environment(),
+            provenance,
+        })
+    }
+}
+
+/// An os-info document resolved from one or more layers
+#[derive(Debug)]
+pub struct LoadedOsInfo {
+    /// The resolved document
+    pub info: OsInfo,
+    /// Layers that were applied, in order
+    pub layers: Vec<Layer>,
+    /// Container and chroot status of the root the layers were read from
+    pub environment: Environment,
+    /// JSON pointer of each resolved value mapped to the index of its layer
+    provenance: BTreeMap<String, usize>,
+}
+
+impl LoadedOsInfo {
+    /// Returns the layer that provided a value, by JSON pointer or dotted path
+    ///
+    /// Values nested inside arrays resolve to the layer that set the array.
+    pub fn source_of(&self, path: &str) -> Option<&Layer> {
+        let pointer = json_pointer(path);
+        let mut pointer = pointer.as_str();
+        loop {
+            if let Some(&index) = self.provenance.get(pointer) {
+                return self.layers.get(index);
+            }
+            pointer = &pointer[..pointer.rfind('/')?];
+        }
+    }
+
+    /// Iterates all resolved value pointers with their originating layer
+    pub fn provenance(&self) -> impl Iterator<Item = (&str, &Layer)> {
+        self.provenance
+            .iter()
+            .map(|(pointer, &index)| (pointer.as_str(), &self.layers[index]))
+    }
+}
+
+/// Check a parsed layer against its detached signature
+fn verify_layer(keyring: &Keyring, path: &Path, layer: &Value) -> Result<KeyId, Error> {
+    let signature = Signature::read_for(path).context(VerifySnafu { path })?;
+    keyring
+        .verify(layer, &signature)
+        .context(VerifySnafu { path })
+}
+
+/// Apply an RFC 7396 merge patch, recording which layer set each leaf value
+fn merge_patch(
+    target: &mut Value,
+    patch: Value,
+    pointer: &str,
+    layer: usize,
+    provenance: &mut BTreeMap<String, usize>,
+) {
+    let Value::Object(patch) = patch else {
+        forget(provenance, pointer);
+        provenance.insert(pointer.to_owned(), layer);
+        *target = patch;
+        return;
+    };
+
+    if !target.is_object() {
+        forget(provenance, pointer);
+        *target = Value::Object(Map::new());
+    }
+    let Value::Object(map) = target else {
+        unreachable!()
+    };
+
+    for (key, value) in patch {
+        let child = format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"));
+        if value.is_null() {
+            map.remove(&key);
+            forget(provenance, &child);
+        } else {
+            merge_patch(
+                map.entry(key).or_insert(Value::Null),
+                value,
+                &child,
+                layer,
+                provenance,
+            );
+        }
+    }
+}
+
+/// Drop provenance for a pointer and everything below it
+fn forget(provenance: &mut BTreeMap<String, usize>, pointer: &str) {
+    let prefix = format!("{pointer}/");
+    provenance.retain(|k, _| k != pointer && !k.starts_with(&prefix));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tree(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("os-info-loader-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(DROP_IN_DIR)).unwrap();
+        fs::create_dir_all(root.join("usr/lib")).unwrap();
+        fs::copy("../../sample.json", root.join(VENDOR_PATH)).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_merge_patch() {
+        let mut provenance = BTreeMap::new();
+        let mut doc = Value::Null;
+        merge_patch(
+            &mut doc,
+            json!({"a": {"b": 1, "c": [1, 2]}, "d": "x"}),
+            "",
+            0,
+            &mut provenance,
+        );
+        merge_patch(
+            &mut doc,
+            json!({"a": {"b": null, "c": [3]}, "e/f": true}),
+            "",
+            1,
+            &mut provenance,
+        );
+
+        assert_eq!(doc, json!({"a": {"c": [3]}, "d": "x", "e/f": true}));
+        assert_eq!(provenance.get("/a/c"), Some(&1));
+        assert_eq!(provenance.get("/d"), Some(&0));
+        assert_eq!(provenance.get("/e~1f"), Some(&1));
+        assert!(!provenance.contains_key("/a/b"));
+    }
+
+    #[test]
+    fn test_vendor_only() {
+        let root = tree("vendor");
+        let loaded = Loader::with_root(&root).load().unwrap();
+
+        assert_eq!(loaded.layers.len(), 1);
+        assert_eq!(loaded.info.metadata.identity.id, "aerynos");
+        assert_eq!(
+            loaded.source_of("/metadata/identity/name").map(|l| l.kind),
+            Some(LayerKind::Vendor)
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_os_definition() {
+        let root = tree("definition");
+        let definitions = root.join(OS_DEFINITIONS_DIR);
+        fs::create_dir_all(&definitions).unwrap();
+        fs::rename(root.join(VENDOR_PATH), definitions.join("aerynos.json")).unwrap();
+
+        // Without os-release there is no way to pick a definition
+        assert!(matches!(
+            Loader::with_root(&root).load(),
+            Err(Error::NoDocument { .. })
+        ));
+
+        fs::write(root.join(VENDOR_OS_RELEASE_PATH), "ID=aerynos\n").unwrap();
+        let loaded = Loader::with_root(&root).load().unwrap();
+        assert_eq!(loaded.layers[0].kind, LayerKind::Vendor);
+        assert_eq!(loaded.layers[0].path, definitions.join("aerynos.json"));
+
+        // A vendor document takes precedence over the definition
+        fs::write(root.join(VENDOR_PATH), r#"{"metadata": {}}"#).unwrap();
+        let layers = Loader::with_root(&root).layers().unwrap();
+        assert_eq!(layers.len(), 1);
+        assert_eq!(layers[0].path, root.join(VENDOR_PATH));
+
+        fs::write(root.join(OS_RELEASE_PATH), "ID=../../../etc/passwd\n").unwrap();
+        fs::remove_file(root.join(VENDOR_PATH)).unwrap();
+        assert!(Loader::with_root(&root).layers().unwrap().is_empty());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_resolved_document() {
+        let root = tree("invalid");
+        fs::write(
+            root.join(LOCAL_PATH),
+            r#"{"metadata": {"identity": {"name": 42}}}"#,
+        )
+        .unwrap();
+
+        let err = Loader::with_root(&root).load().unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains(&root.join(VENDOR_PATH).display().to_string()));
+        assert!(message.contains(&root.join(LOCAL_PATH).display().to_string()));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_local_overlays() {
+        let root = tree("overlay");
+        fs::write(
+            root.join(LOCAL_PATH),
+            r#"{"metadata": {"identity": {"display": "AerynOS (lab)"}}}"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join(DROP_IN_DIR).join("20-variant.json"),
+            r#"{"system": {"platform": {"variant": "server"}}}"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join(DROP_IN_DIR).join("10-variant.json"),
+            r#"{"system": {"platform": {"variant": "workstation"}}, "resources": {"social": {"youtube": null}}}"#,
+        )
+        .unwrap();
+        fs::write(root.join(DROP_IN_DIR).join("README"), "ignored").unwrap();
+
+        let loaded = Loader::with_root(&root).load().unwrap();
+        let kinds = loaded.layers.iter().map(|l| l.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                LayerKind::Vendor,
+                LayerKind::Local,
+                LayerKind::DropIn,
+                LayerKind::DropIn
+            ]
+        );
+
+        assert_eq!(loaded.info.metadata.identity.display, "AerynOS (lab)");
+        assert_eq!(loaded.info.metadata.identity.name, "AerynOS");
+        assert_eq!(loaded.info.system.platform.variant, "server");
+        assert!(!loaded.info.resources.social.contains_key("youtube"));
+
+        let source = loaded.source_of("/system/platform/variant").unwrap();
+        assert_eq!(source.path, root.join(DROP_IN_DIR).join("20-variant.json"));
+        assert_eq!(
+            loaded
+                .source_of("/metadata/identity/display")
+                .map(|l| l.kind),
+            Some(LayerKind::Local)
+        );
+        assert_eq!(
+            loaded.source_of("system.platform.variant").map(|l| l.kind),
+            Some(LayerKind::DropIn)
+        );
+        assert_eq!(
+            loaded
+                .source_of("/system/composition/technology/core/0")
+                .map(|l| l.kind),
+            Some(LayerKind::Vendor)
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_missing_document() {
+        let root =
+            std::env::temp_dir().join(format!("os-info-loader-empty-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+
+        assert!(matches!(
+            Loader::with_root(&root).load(),
+            Err(Error::NoDocument { .. })
+        ));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_verified() {
+        let root = tree("verified");
+        let key = crate::SecretKey::from_seed(&[1; 32]);
+
+        // Unsigned layers are rejected
+        assert!(matches!(
+            Loader::with_root(&root).verified().load(),
+            Err(Error::Verify {
+                source: crate::SignatureError::MissingSignature { .. },
+                ..
+            })
+        ));
+
+        fs::write(
+            Signature::path_for(root.join(VENDOR_PATH)),
+            key.sign_file(root.join(VENDOR_PATH)).unwrap().to_string(),
+        )
+        .unwrap();
+
+        // Signed by a key that isn't trusted yet
+        assert!(matches!(
+            Loader::with_root(&root).verified().load(),
+            Err(Error::Verify { .. })
+        ));
+
+        let keys = root.join(crate::VENDOR_KEYS_DIR);
+        fs::create_dir_all(&keys).unwrap();
+        fs::write(keys.join("vendor.pub"), key.public_key().to_string()).unwrap();
+
+        let loaded = Loader::with_root(&root).verified().load().unwrap();
+        assert_eq!(loaded.layers[0].signed_by, Some(key.id()));
+
+        // Local modifications break verification unless re-signed
+        fs::write(
+            root.join(LOCAL_PATH),
+            r#"{"security_contact": {"email": "attacker@example.com"}}"#,
+        )
+        .unwrap();
+        assert!(Loader::with_root(&root).verified().load().is_err());
+        assert!(Loader::with_root(&root).load().is_ok());
+
+        // Layers are verified as written, not as the typed document would
+        // serialise them
+        fs::write(
+            root.join(LOCAL_PATH),
+            r#"{"metadata": {"version": {"released": "2025-03-18T02:00:00+02:00", "codename": null}}}"#,
+        )
+        .unwrap();
+        fs::write(
+            Signature::path_for(root.join(LOCAL_PATH)),
+            key.sign_file(root.join(LOCAL_PATH)).unwrap().to_string(),
+        )
+        .unwrap();
+        let loaded = Loader::with_root(&root).verified().load().unwrap();
+        assert_eq!(loaded.layers[1].signed_by, Some(key.id()));
+
+        // Keys in /etc are only trusted when asked for
+        let local_key = crate::SecretKey::from_seed(&[2; 32]);
+        let local_keys = root.join(crate::LOCAL_KEYS_DIR);
+        fs::create_dir_all(&local_keys).unwrap();
+        fs::write(
+            local_keys.join("local.pub"),
+            local_key.public_key().to_string(),
+        )
+        .unwrap();
+        fs::write(
+            Signature::path_for(root.join(LOCAL_PATH)),
+            local_key
+                .sign_file(root.join(LOCAL_PATH))
+                .unwrap()
+                .to_string(),
+        )
+        .unwrap();
+        assert!(matches!(
+            Loader::with_root(&root).verified().load(),
+            Err(Error::Verify {
+                source: crate::SignatureError::UntrustedKey { .. },
+                ..
+            })
+        ));
+        let loaded = Loader::with_root(&root).trust_local_keys().load().unwrap();
+        assert_eq!(loaded.layers[1].signed_by, Some(local_key.id()));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_container_host() {
+        let root = tree("container");
+        let loader = Loader::with_root(&root);
+
+        let loaded = loader.load().unwrap();
+        assert_eq!(loaded.environment, Environment::default());
+        assert!(loader.host().is_none());
+
+        fs::write(root.join(crate::DOCKERENV_PATH), "").unwrap();
+        assert_eq!(
+            loader.load().unwrap().environment.container,
+            Some(crate::ContainerRuntime::Docker)
+        );
+        // No host root is mounted
+        assert!(loader.host().is_none());
+
+        let host_root = root.join("run/host");
+        fs::create_dir_all(host_root.join("usr/lib")).unwrap();
+        fs::write(
+            host_root.join(VENDOR_PATH),
+            fs::read_to_string(root.join(VENDOR_PATH))
+                .unwrap()
+                .replace("\"id\": \"aerynos\"", "\"id\": \"hostos\""),
+        )
+        .unwrap();
+
+        let host = loader.host().unwrap();
+        assert_eq!(host.root(), host_root);
+        assert_eq!(host.load().unwrap().info.metadata.identity.id, "hostos");
+        assert_eq!(loader.load().unwrap().info.metadata.identity.id, "aerynos");
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,352 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Localised strings
+//!
+//! Translatable fields may carry per-locale variants next to the untranslated
+//! value, using the desktop entry `field[locale]` style:
+//!
+//! ```json
+//! "name": "AerynOS",
+//! "display": "AerynOS 0.25.1",
+//! "display[de]": "AerynOS 0.25.1 (Deutsch)"
+//! ```
+//!
+//! Lookups take a list of requested locales in order of preference, and fall
+//! back from `de_DE@euro` through `de_DE` and `de@euro` to `de` before moving
+//! on to the next locale, and finally to the untranslated value.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Extensions, Identity, TechnologyCapabilities, Website};
+
+/// Per-locale variants of translatable fields
+///
+/// Maps a field name to its translations, keyed by locale. Serialised as flat
+/// `field[locale]` keys alongside the untranslated fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Translations(BTreeMap<String, BTreeMap<String, String>>);
+
+impl Translations {
+    /// Creates an empty set of translations
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether no translations are present
+    pub fn is_empty(&self) -> bool {
+        self.0.values().all(BTreeMap::is_empty)
+    }
+
+    /// Sets the translation of `field` for `locale`
+    pub fn insert(
+        &mut self,
+        field: impl Into<String>,
+        locale: impl Into<String>,
+        text: impl Into<String>,
+    ) {
+        self.0
+            .entry(field.into())
+            .or_default()
+            .insert(locale.into(), text.into());
+    }
+
+    /// Translation of `field` for exactly `locale`
+    pub fn get(&self, field: &str, locale: &str) -> Option<&str> {
+        self.0.get(field)?.get(locale).map(String::as_str)
+    }
+
+    /// All translations of `field`, as `(locale, text)` pairs in locale order
+    pub fn of(&self, field: &str) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .get(field)
+            .into_iter()
+            .flatten()
+            .map(|(locale, text)| (locale.as_str(), text.as_str()))
+    }
+
+    /// Best translation of `field` for the requested locales
+    ///
+    /// Returns `None` when no requested locale has a translation, or when a
+    /// `C`/`POSIX` locale is reached first, meaning the untranslated value
+    /// should be used.
+    pub fn resolve<S: AsRef<str>>(&self, field: &str, locales: &[S]) -> Option<&str> {
+        let translations = self.0.get(field)?;
+
+        for locale in locales {
+            let locale = locale.as_ref();
+            if matches!(locale, "C" | "POSIX") || locale.starts_with("C.") {
+                return None;
+            }
+            for candidate in locale_candidates(locale) {
+                if let Some(text) = translations.get(&candidate) {
+                    return Some(text);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Resolves `field`, falling back to the untranslated `default`
+    pub fn localize<'a, S: AsRef<str>>(
+        &'a self,
+        field: &str,
+        default: &'a str,
+        locales: &[S],
+    ) -> &'a str {
+        self.resolve(field, locales).unwrap_or(default)
+    }
+}
+
+/// Locale names to try for `locale`, most specific first
+///
+/// `de_DE.UTF-8@euro` yields `de_DE@euro`, `de_DE`, `de@euro` and `de`. BCP 47
+/// tags such as `pt-BR` are accepted and treated as `pt_BR`.
+pub fn locale_candidates(locale: &str) -> Vec<String> {
+    let locale = locale.replace('-', "_");
+    let (locale, modifier) = match locale.split_once('@') {
+        Some((locale, modifier)) => (locale, Some(modifier)),
+        None => (locale.as_str(), None),
+    };
+    let locale = locale.split('.').next().unwrap_or_default();
+    let (language, country) = match locale.split_once('_') {
+        Some((language, country)) => (language, Some(country)),
+        None => (locale, None),
+    };
+    if language.is_empty() {
+        return vec![];
+    }
+
+    let mut candidates = vec![];
+    if let Some(country) = country {
+        if let Some(modifier) = modifier {
+            candidates.push(format!("{language}_{country}@{modifier}"));
+        }
+        candidates.push(format!("{language}_{country}"));
+    }
+    if let Some(modifier) = modifier {
+        candidates.push(format!("{language}@{modifier}"));
+    }
+    candidates.push(language.to_string());
+    candidates
+}
+
+/// Requested locales from the environment, in order of preference
+///
+/// Follows gettext: the colon separated `LANGUAGE` list comes first, then the
+/// first of `LC_ALL`, `LC_MESSAGES` and `LANG` that is set. `LANGUAGE` is
+/// ignored when that locale is `C` or `POSIX`.
+pub fn locales_from_env() -> Vec<String> {
+    locales_from_vars(|name| std::env::var(name).ok())
+}
+
+fn locales_from_vars(var: impl Fn(&str) -> Option<String>) -> Vec<String> {
+    let Some(locale) = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(&var)
+        .find(|value| !value.is_empty())
+    else {
+        return vec![];
+    };
+    if matches!(locale.as_str(), "C" | "POSIX") || locale.starts_with("C.") {
+        return vec![locale];
+    }
+
+    let mut locales = var("LANGUAGE")
+        .unwrap_or_default()
+        .split(':')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    locales.push(locale);
+    locales
+}
+
+/// Splits a `field[locale]` key
+fn split_key(key: &str) -> Option<(&str, &str)> {
+    let (field, rest) = key.split_once('[')?;
+    let locale = rest.strip_suffix(']')?;
+
+    let valid_field =
+        !field.is_empty() && field.chars().all(|c| c.is_ascii_lowercase() || c == '_');
+    let valid_locale = !locale.is_empty()
+        && locale
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_@.-".contains(c));
+    (valid_field && valid_locale).then_some((field, locale))
+}
+
+/// Whether `key` names a translation, e.g. `display[de]`
+pub fn is_translation_key(key: &str) -> bool {
+    split_key(key).is_some()
+}
+
+impl Serialize for Translations {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        for (field, translations) in &self.0 {
+            for (locale, text) in translations {
+                map.serialize_entry(&format!("{field}[{locale}]"), text)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Translations {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TranslationsVisitor;
+
+        impl<'de> Visitor<'de> for TranslationsVisitor {
+            type Value = Translations;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a map of `field[locale]` keys")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut translations = Translations::new();
+                while let Some((key, value)) = map.next_entry::<String, serde_json::Value>()? {
+                    // Other keys are left for the remaining fields
+                    if let (Some((field, locale)), Some(text)) = (split_key(&key), value.as_str()) {
+                        translations.insert(field, locale, text);
+                    }
+                }
+                Ok(translations)
+            }
+        }
+
+        deserializer.deserialize_map(TranslationsVisitor)
+    }
+}
+
+/// Deserialises flattened extensions, leaving out translation keys
+pub(crate) fn extensions_without_translations<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Extensions, D::Error> {
+    let mut extensions = Extensions::deserialize(deserializer)?;
+    extensions.retain(|key, value| !(is_translation_key(key) && value.is_string()));
+    Ok(extensions)
+}
+
+impl Identity {
+    /// Full name in the best of the requested locales
+    pub fn localized_name<S: AsRef<str>>(&self, locales: &[S]) -> &str {
+        self.translations.localize("name", &self.name, locales)
+    }
+
+    /// Display name in the best of the requested locales
+    pub fn localized_display<S: AsRef<str>>(&self, locales: &[S]) -> &str {
+        self.translations
+            .localize("display", &self.display, locales)
+    }
+}
+
+impl Website {
+    /// Display name in the best of the requested locales
+    pub fn localized_display_name<S: AsRef<str>>(&self, locales: &[S]) -> &str {
+        self.translations
+            .localize("display_name", &self.display_name, locales)
+    }
+}
+
+impl TechnologyCapabilities {
+    /// Description in the best of the requested locales
+    pub fn localized_description<S: AsRef<str>>(&self, locales: &[S]) -> &str {
+        self.translations
+            .localize("description", &self.description, locales)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_locale_candidates() {
+        assert_eq!(
+            locale_candidates("de_DE.UTF-8@euro"),
+            vec!["de_DE@euro", "de_DE", "de@euro", "de"]
+        );
+        assert_eq!(locale_candidates("pt-BR"), vec!["pt_BR", "pt"]);
+        assert_eq!(locale_candidates("fr"), vec!["fr"]);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut translations = Translations::new();
+        translations.insert("name", "de", "Deutsch");
+        translations.insert("name", "pt_BR", "Português do Brasil");
+
+        assert_eq!(
+            translations.resolve("name", &["de_AT.UTF-8"]),
+            Some("Deutsch")
+        );
+        assert_eq!(
+            translations.resolve("name", &["fr_FR", "pt_BR"]),
+            Some("Português do Brasil")
+        );
+        assert_eq!(translations.resolve("name", &["pt_PT"]), None);
+        assert_eq!(translations.resolve("name", &["C", "de"]), None);
+        assert_eq!(translations.localize("name", "English", &["es"]), "English");
+        assert_eq!(translations.resolve("display", &["de"]), None);
+    }
+
+    #[test]
+    fn test_locales_from_vars() {
+        let vars = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+
+        assert_eq!(
+            locales_from_vars(vars(&[("LANG", "de_DE.UTF-8"), ("LANGUAGE", "fr:en")])),
+            vec!["fr", "en", "de_DE.UTF-8"]
+        );
+        assert_eq!(
+            locales_from_vars(vars(&[("LC_ALL", "C"), ("LANGUAGE", "fr")])),
+            vec!["C"]
+        );
+        assert!(locales_from_vars(vars(&[])).is_empty());
+    }
+
+    #[test]
+    fn test_identity_round_trip() {
+        let document = json!({
+            "id": "aerynos",
+            "name": "AerynOS",
+            "name[de]": "AerynOS DE",
+            "display": "AerynOS 0.25.1",
+            "display[pt_BR]": "AerynOS 0.25.1 (Brasil)",
+            "x-aerynos-codename-art": "wings.svg",
+            "x-acme-tags[0]": { "not": "a translation" }
+        });
+
+        let identity: Identity = serde_json::from_value(document.clone()).unwrap();
+        assert_eq!(identity.localized_name(&["de_DE"]), "AerynOS DE");
+        assert_eq!(
+            identity.localized_display(&["pt_BR.UTF-8"]),
+            "AerynOS 0.25.1 (Brasil)"
+        );
+        assert_eq!(identity.localized_display(&["de"]), "AerynOS 0.25.1");
+        assert!(!identity.extensions.contains_key("name[de]"));
+        assert!(identity.extensions.contains_key("x-aerynos-codename-art"));
+        assert!(identity.extensions.contains_key("x-acme-tags[0]"));
+
+        let mut saved = serde_json::to_value(&identity).unwrap();
+        saved.as_object_mut().unwrap().remove("former_identities");
+        assert_eq!(saved, document);
+    }
+}
@@ -0,0 +1,125 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Conversion support for lsb-release files
+//!
+//! Legacy software reads `/etc/lsb-release` or runs `lsb_release` to identify
+//! the distribution. This module maps os-info onto the four `DISTRIB_*`
+//! fields those tools expect, and parses existing files.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::OsInfo;
+use crate::os_release::{OsReleaseError, parse_assignments, shell_escape};
+
+/// Location of the lsb-release file, relative to the root
+pub const LSB_RELEASE_PATH: &str = "etc/lsb-release";
+
+/// Represents the fields of an lsb-release file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LsbRelease {
+    /// Distributor name, e.g. `AerynOS`
+    pub distrib_id: String,
+    /// Release number
+    pub distrib_release: String,
+    /// Release codename
+    pub distrib_codename: Option<String>,
+    /// Human readable description of the release
+    pub distrib_description: String,
+}
+
+/// Convert an OSInfo into lsb-release fields
+impl From<&OsInfo> for LsbRelease {
+    fn from(info: &OsInfo) -> Self {
+        Self {
+            distrib_id: info.metadata.identity.name.clone(),
+            distrib_release: info.metadata.version.short.clone(),
+            distrib_codename: info.metadata.version.codename.clone().into_option(),
+            distrib_description: info.metadata.identity.display.clone(),
+        }
+    }
+}
+
+impl Display for LsbRelease {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "DISTRIB_ID={}", shell_escape(&self.distrib_id))?;
+        writeln!(f, "DISTRIB_RELEASE={}", shell_escape(&self.distrib_release))?;
+        if let Some(codename) = &self.distrib_codename {
+            writeln!(f, "DISTRIB_CODENAME={}", shell_escape(codename))?;
+        }
+        writeln!(
+            f,
+            "DISTRIB_DESCRIPTION={}",
+            shell_escape(&self.distrib_description)
+        )
+    }
+}
+
+/// Parse an lsb-release file, ignoring unknown keys
+impl FromStr for LsbRelease {
+    type Err = OsReleaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut release = Self::default();
+
+        for (key, value) in parse_assignments(s)? {
+            match key.as_str() {
+                "DISTRIB_ID" => release.distrib_id = value,
+                "DISTRIB_RELEASE" => release.distrib_release = value,
+                "DISTRIB_CODENAME" => release.distrib_codename = Some(value),
+                "DISTRIB_DESCRIPTION" => release.distrib_description = value,
+                _ => {}
+            }
+        }
+
+        Ok(release)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Nullable, load_os_info};
+
+    #[test]
+    fn test_lsb_release() {
+        let mut info = load_os_info(include_str!("../../../sample.json")).unwrap();
+        info.metadata.version.codename = Nullable::Value("aurora".to_string());
+
+        let release = LsbRelease::from(&info);
+        assert_eq!(
+            release.to_string(),
+            "DISTRIB_ID=\"AerynOS\"\nDISTRIB_RELEASE=\"0.25.1\"\nDISTRIB_CODENAME=\"aurora\"\nDISTRIB_DESCRIPTION=\"AerynOS 0.25.1\"\n"
+        );
+        assert_eq!(release.to_string().parse::<LsbRelease>().unwrap(), release);
+    }
+
+    #[test]
+    fn test_escaping() {
+        let release = LsbRelease {
+            distrib_id: "Aeryn OS".to_string(),
+            distrib_release: "1; rm -rf /".to_string(),
+            distrib_codename: Some("$(whoami)".to_string()),
+            distrib_description: "`id` \\ \"quoted\"".to_string(),
+        };
+
+        let output = release.to_string();
+        assert_eq!(
+            output,
+            "DISTRIB_ID=\"Aeryn OS\"\nDISTRIB_RELEASE=\"1; rm -rf /\"\nDISTRIB_CODENAME=\"\\$(whoami)\"\nDISTRIB_DESCRIPTION=\"\\`id\\` \\\\ \\\"quoted\\\"\"\n"
+        );
+        assert_eq!(output.parse::<LsbRelease>().unwrap(), release);
+    }
+
+    #[test]
+    fn test_parse() {
+        let release = "DISTRIB_ID=Ubuntu\nDISTRIB_RELEASE=24.04\nDISTRIB_CODENAME=noble\nDISTRIB_DESCRIPTION=\"Ubuntu 24.04.1 LTS\"\n"
+            .parse::<LsbRelease>()
+            .unwrap();
+        assert_eq!(release.distrib_id, "Ubuntu");
+        assert_eq!(release.distrib_codename.as_deref(), Some("noble"));
+        assert_eq!(release.distrib_description, "Ubuntu 24.04.1 LTS");
+    }
+}
@@ -0,0 +1,114 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Optional fields that may be written as an explicit `null`
+//!
+//! Most optional fields are left out when unset. For the few where `null`
+//! carries meaning, such as a release without a codename or a maintainer who
+//! hasn't left, [`Nullable`] remembers whether the document wrote `null` or
+//! omitted the key, so saving a loaded document writes it back the same way.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An optional value that tells an explicit `null` apart from a missing key
+///
+/// Fields of this type use `#[serde(default, skip_serializing_if =
+/// "Nullable::is_absent")]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Nullable<T> {
+    /// The key is not present
+    #[default]
+    Absent,
+    /// The key is present with a `null` value
+    Null,
+    /// The key has a value
+    Value(T),
+}
+
+impl<T> Nullable<T> {
+    /// Whether the key is missing from the document
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Nullable::Absent)
+    }
+
+    /// Whether there is a value
+    pub fn is_some(&self) -> bool {
+        matches!(self, Nullable::Value(_))
+    }
+
+    /// Whether there is no value, either absent or `null`
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    /// The value, if any
+    pub fn as_ref(&self) -> Option<&T> {
+        match self {
+            Nullable::Value(value) => Some(value),
+            Nullable::Absent | Nullable::Null => None,
+        }
+    }
+
+    /// The value dereferenced, e.g. `Option<&str>` for a `Nullable<String>`
+    pub fn as_deref(&self) -> Option<&T::Target>
+    where
+        T: std::ops::Deref,
+    {
+        self.as_ref().map(|value| &**value)
+    }
+
+    /// Converts into an `Option`, dropping the distinction between absent and `null`
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Nullable::Value(value) => Some(value),
+            Nullable::Absent | Nullable::Null => None,
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Nullable<T> {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Nullable::Absent, Nullable::Value)
+    }
+}
+
+impl<T: Serialize> Serialize for Nullable<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Nullable::Value(value) => serializer.serialize_some(value),
+            Nullable::Absent | Nullable::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Nullable<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Only called for keys that are present, missing ones use the default
+        Ok(Option::deserialize(deserializer)?.map_or(Nullable::Null, Nullable::Value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Fields {
+        #[serde(default, skip_serializing_if = "Nullable::is_absent")]
+        codename: Nullable<String>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for json in [r#"{}"#, r#"{"codename":null}"#, r#"{"codename":"wings"}"#] {
+            let fields: Fields = serde_json::from_str(json).unwrap();
+            assert_eq!(serde_json::to_string(&fields).unwrap(), json);
+        }
+
+        let fields: Fields = serde_json::from_str(r#"{"codename":null}"#).unwrap();
+        assert_eq!(fields.codename, Nullable::Null);
+        assert_eq!(fields.codename.as_deref(), None);
+        assert!(fields.codename.is_none() && !fields.codename.is_absent());
+    }
+}
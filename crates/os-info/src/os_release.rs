@@ -5,13 +5,35 @@
 //! Conversion support for os-release files
 //!
 //! This module provides functionality to convert OSInfo into os-release format
-//! for system compatibility purposes.
+//! for system compatibility purposes, and to parse existing os-release files.
+//! The same conversion produces `/etc/initrd-release` content for initramfs
+//! images, see [`OsRelease::initrd`].
 
 use std::collections::HashMap;
 use std::fmt::Display;
+use std::str::FromStr;
+
+use snafu::Snafu;
 
 use crate::OsInfo;
 
+/// Location of the os-release file, relative to the root
+pub const OS_RELEASE_PATH: &str = "etc/os-release";
+/// Vendor location of the os-release file, used when `/etc/os-release` is absent
+pub const VENDOR_OS_RELEASE_PATH: &str = "usr/lib/os-release";
+/// Location of the os-release file inside an initramfs, relative to its root
+pub const INITRD_RELEASE_PATH: &str = "etc/initrd-release";
+
+/// Errors raised while parsing os-release style files
+#[derive(Debug, Snafu)]
+pub enum OsReleaseError {
+    #[snafu(display("line {line}: expected `KEY=value`, found `{content}`"))]
+    InvalidLine { line: usize, content: String },
+
+    #[snafu(display("line {line}: unterminated quote in `{content}`"))]
+    UnterminatedQuote { line: usize, content: String },
+}
+
 /// Represents a parsed os-release file with strongly typed standard fields
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OsRelease {
@@ -27,6 +49,8 @@ pub struct OsRelease {
     pub pretty_name: String,
     /// Space-separated list of operating system identifiers that this OS is based on
     pub id_like: Option<String>,
+    /// CPE name of the operating system
+    pub cpe_name: Option<String>,
     /// Home website URL
     pub home_url: Option<String>,
     /// Documentation URL
@@ -55,6 +79,7 @@ impl OsRelease {
             version,
             pretty_name,
             id_like: None,
+            cpe_name: None,
             home_url: None,
             documentation_url: None,
             support_url: None,
@@ -64,6 +89,81 @@ impl OsRelease {
     }
 }
 
+/// Options for generating initrd-release content
+#[derive(Debug, Clone)]
+pub struct InitrdReleaseOptions {
+    /// Appended to `PRETTY_NAME`
+    pub pretty_name_suffix: Option<String>,
+    /// `VARIANT_ID` of the initramfs
+    pub variant_id: Option<String>,
+    /// Human readable `VARIANT` of the initramfs
+    pub variant: Option<String>,
+    /// Fields to set after conversion, replacing generated values
+    pub overrides: HashMap<String, String>,
+}
+
+impl Default for InitrdReleaseOptions {
+    fn default() -> Self {
+        Self {
+            pretty_name_suffix: Some(" (Initramfs)".to_string()),
+            variant_id: Some("initrd".to_string()),
+            variant: Some("Initramfs".to_string()),
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl OsRelease {
+    /// Sets a field by its os-release key
+    ///
+    /// Standard keys update the typed fields, anything else is stored in
+    /// `extra_fields`.
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let key = key.into();
+        let value = value.into();
+        match key.as_str() {
+            "NAME" => self.name = value,
+            "ID" => self.id = value,
+            "VERSION_ID" => self.version_id = value,
+            "VERSION" => self.version = value,
+            "PRETTY_NAME" => self.pretty_name = value,
+            "ID_LIKE" => self.id_like = Some(value),
+            "CPE_NAME" => self.cpe_name = Some(value),
+            "HOME_URL" => self.home_url = Some(value),
+            "DOCUMENTATION_URL" => self.documentation_url = Some(value),
+            "SUPPORT_URL" => self.support_url = Some(value),
+            "BUG_REPORT_URL" => self.bug_report_url = Some(value),
+            _ => {
+                self.extra_fields.insert(key, value);
+            }
+        }
+    }
+
+    /// Creates initrd-release content for an initramfs of this OS
+    ///
+    /// The identity and version match the os-release of the installed system,
+    /// so tools can tell which OS an initramfs belongs to, while the variant
+    /// and pretty name mark it as an initramfs. Overrides are applied last.
+    pub fn initrd(info: &OsInfo, options: &InitrdReleaseOptions) -> Self {
+        let mut release = Self::from(info);
+
+        if let Some(suffix) = &options.pretty_name_suffix {
+            release.pretty_name.push_str(suffix);
+        }
+        if let Some(variant_id) = &options.variant_id {
+            release.set("VARIANT_ID", variant_id);
+        }
+        if let Some(variant) = &options.variant {
+            release.set("VARIANT", variant);
+        }
+        for (key, value) in &options.overrides {
+            release.set(key, value);
+        }
+
+        release
+    }
+}
+
 impl Display for OsRelease {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Write required fields
@@ -77,6 +177,9 @@ impl Display for OsRelease {
         if let Some(ref id_like) = self.id_like {
             writeln!(f, "ID_LIKE={}", shell_escape(id_like))?;
         }
+        if let Some(ref cpe_name) = self.cpe_name {
+            writeln!(f, "CPE_NAME={}", shell_escape(cpe_name))?;
+        }
         if let Some(ref url) = self.home_url {
             writeln!(f, "HOME_URL={}", shell_escape(url))?;
         }
@@ -92,7 +195,7 @@ impl Display for OsRelease {
 
         // Write any extra fields
         let mut extra: Vec<_> = self.extra_fields.iter().collect();
-        extra.sort_by(|(a, _), (b, _)| a.cmp(b));
+        extra.sort_by_key(|(a, _)| *a);
         for (key, value) in extra {
             writeln!(f, "{}={}", key, shell_escape(value))?;
         }
@@ -101,6 +204,31 @@ impl Display for OsRelease {
     }
 }
 
+/// Parse an os-release file
+///
+/// Missing fields take the defaults from os-release(5): `NAME` and
+/// `PRETTY_NAME` default to `Linux`, `ID` to `linux`, and the versions to
+/// empty strings. Unknown keys are kept in `extra_fields`.
+impl FromStr for OsRelease {
+    type Err = OsReleaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut release = OsRelease::new(
+            "Linux".to_string(),
+            "linux".to_string(),
+            String::new(),
+            String::new(),
+            "Linux".to_string(),
+        );
+
+        for (key, value) in parse_assignments(s)? {
+            release.set(key, value);
+        }
+
+        Ok(release)
+    }
+}
+
 /// Convert an OSInfo into an os-release format
 impl From<&OsInfo> for OsRelease {
     fn from(info: &OsInfo) -> Self {
@@ -114,6 +242,13 @@ impl From<&OsInfo> for OsRelease {
 
         // Set optional fields
         release.id_like = info.metadata.identity.id_like.clone();
+        release.cpe_name = info.cpe().map(|cpe| cpe.to_uri());
+
+        if let Some(level) = &info.metadata.version.sysext_level {
+            release
+                .extra_fields
+                .insert("SYSEXT_LEVEL".to_string(), level.clone());
+        }
 
         // Set ANSI color if available
         if let Some(ansi_color) = &info.metadata.identity.ansi_color {
@@ -126,31 +261,31 @@ impl From<&OsInfo> for OsRelease {
         for site in info.resources.websites.values() {
             match site.scope {
                 crate::WebsiteScope::Home => {
-                    release.home_url = Some(site.url.clone());
+                    release.home_url = Some(site.url.to_string());
                 }
                 crate::WebsiteScope::Support => {
-                    release.support_url = Some(site.url.clone());
+                    release.support_url = Some(site.url.to_string());
                 }
                 crate::WebsiteScope::BugTracker => {
-                    release.bug_report_url = Some(site.url.clone());
+                    release.bug_report_url = Some(site.url.to_string());
                 }
                 crate::WebsiteScope::Documentation => {
-                    release.documentation_url = Some(site.url.clone());
+                    release.documentation_url = Some(site.url.to_string());
                 }
                 crate::WebsiteScope::PrivacyPolicy => {
                     release
                         .extra_fields
-                        .insert("PRIVACY_POLICY_URL".to_string(), site.url.clone());
+                        .insert("PRIVACY_POLICY_URL".to_string(), site.url.to_string());
                 }
                 crate::WebsiteScope::TermsOfService => {
                     release
                         .extra_fields
-                        .insert("TERMS_OF_SERVICE_URL".to_string(), site.url.clone());
+                        .insert("TERMS_OF_SERVICE_URL".to_string(), site.url.to_string());
                 }
                 crate::WebsiteScope::SecurityPolicy => {
                     release
                         .extra_fields
-                        .insert("SECURITY_POLICY_URL".to_string(), site.url.clone());
+                        .insert("SECURITY_POLICY_URL".to_string(), site.url.to_string());
                 }
                 _ => {}
             }
@@ -161,7 +296,7 @@ impl From<&OsInfo> for OsRelease {
             if let Some(email) = &contact.email {
                 release
                     .extra_fields
-                    .insert("SECURITY_CONTACT".to_string(), email.clone());
+                    .insert("SECURITY_CONTACT".to_string(), email.to_string());
             }
         }
 
@@ -169,9 +304,90 @@ impl From<&OsInfo> for OsRelease {
     }
 }
 
-/// Escape special characters for shell compatibility
-fn shell_escape(s: &str) -> String {
-    format!("\"{}\"", s.replace('\"', "\\\""))
+/// Double quote a value, escaping the characters that stay special inside
+/// double quotes
+pub(crate) fn shell_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        if matches!(c, '"' | '\\' | '$' | '`') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Parse the `KEY=value` assignments of an os-release style file
+///
+/// Blank lines and `#` comments are skipped. Values may be unquoted, single
+/// quoted, or double quoted with backslash escapes.
+pub(crate) fn parse_assignments(content: &str) -> Result<Vec<(String, String)>, OsReleaseError> {
+    let mut assignments = vec![];
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = || InvalidLineSnafu {
+            line: index + 1,
+            content: raw,
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            return invalid().fail();
+        };
+        let valid_key = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_key {
+            return invalid().fail();
+        }
+
+        let value = unquote(value).ok_or_else(|| {
+            UnterminatedQuoteSnafu {
+                line: index + 1,
+                content: raw,
+            }
+            .build()
+        })?;
+        assignments.push((key.to_string(), value));
+    }
+
+    Ok(assignments)
+}
+
+/// Strip shell quoting from a value
+fn unquote(value: &str) -> Option<String> {
+    let mut chars = value.chars();
+    let mut unquoted = String::new();
+
+    match chars.next() {
+        Some('\'') => {
+            let inner = chars.as_str().strip_suffix('\'')?;
+            unquoted.push_str(inner);
+        }
+        Some('"') => {
+            let inner = chars.as_str().strip_suffix('"')?;
+            let mut chars = inner.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => match chars.next()? {
+                        c @ ('\\' | '"' | '$' | '`') => unquoted.push(c),
+                        c => {
+                            unquoted.push('\\');
+                            unquoted.push(c);
+                        }
+                    },
+                    c => unquoted.push(c),
+                }
+            }
+        }
+        _ => unquoted.push_str(value),
+    }
+
+    Some(unquoted)
 }
 
 #[cfg(test)]
@@ -190,6 +406,15 @@ mod tests {
         assert_eq!(release.version_id, "0.25.1");
         assert_eq!(release.pretty_name, "AerynOS 0.25.1");
         assert_eq!(release.id_like, Some("linux".to_string()));
+        assert_eq!(
+            release.cpe_name.as_deref(),
+            Some("cpe:/o:aerynos:aerynos:0.25.1")
+        );
+        assert!(
+            release
+                .to_string()
+                .contains("CPE_NAME=\"cpe:/o:aerynos:aerynos:0.25.1\"\n")
+        );
     }
 
     #[test]
@@ -214,6 +439,75 @@ mod tests {
         assert_eq!(shell_escape("with \"quotes\""), "\"with \\\"quotes\\\"\"");
     }
 
+    #[test]
+    fn test_parse() {
+        let release = r#"
+# Comment
+NAME="Test OS"
+ID=testos
+ID_LIKE='fedora rhel'
+PRETTY_NAME="Test \"OS\" \$1"
+SYSEXT_LEVEL=2
+"#
+        .parse::<OsRelease>()
+        .unwrap();
+
+        assert_eq!(release.name, "Test OS");
+        assert_eq!(release.id, "testos");
+        assert_eq!(release.id_like.as_deref(), Some("fedora rhel"));
+        assert_eq!(release.pretty_name, "Test \"OS\" $1");
+        assert_eq!(release.version_id, "");
+        assert_eq!(release.extra_fields.get("SYSEXT_LEVEL").unwrap(), "2");
+
+        let info = load_os_info(include_str!("../../../sample.json")).unwrap();
+        let generated = OsRelease::from(&info);
+        assert_eq!(
+            generated.to_string().parse::<OsRelease>().unwrap(),
+            generated
+        );
+
+        assert_eq!(
+            "NAME=\"Test".parse::<OsRelease>().unwrap_err().to_string(),
+            "line 1: unterminated quote in `NAME=\"Test`"
+        );
+        assert_eq!(
+            "not an assignment"
+                .parse::<OsRelease>()
+                .unwrap_err()
+                .to_string(),
+            "line 1: expected `KEY=value`, found `not an assignment`"
+        );
+    }
+
+    #[test]
+    fn test_initrd_release() {
+        let info = load_os_info(include_str!("../../../sample.json")).unwrap();
+
+        let release = OsRelease::initrd(&info, &InitrdReleaseOptions::default());
+        assert_eq!(release.id, "aerynos");
+        assert_eq!(release.version_id, "0.25.1");
+        assert_eq!(release.pretty_name, "AerynOS 0.25.1 (Initramfs)");
+
+        let output = release.to_string();
+        assert!(output.contains("VARIANT_ID=\"initrd\"\n"));
+        assert!(output.contains("VARIANT=\"Initramfs\"\n"));
+
+        let options = InitrdReleaseOptions {
+            pretty_name_suffix: Some(" rescue".to_string()),
+            variant: None,
+            overrides: HashMap::from([
+                ("VARIANT_ID".to_string(), "rescue".to_string()),
+                ("BUILD_ID".to_string(), "dracut-ng".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let release = OsRelease::initrd(&info, &options);
+        assert_eq!(release.pretty_name, "AerynOS 0.25.1 rescue");
+        assert_eq!(release.extra_fields.get("VARIANT_ID").unwrap(), "rescue");
+        assert_eq!(release.extra_fields.get("BUILD_ID").unwrap(), "dracut-ng");
+        assert!(!release.extra_fields.contains_key("VARIANT"));
+    }
+
     #[test]
     fn test_ansi_color() {
         let mut info = load_os_info(include_str!("../../../sample.json")).unwrap();
@@ -229,6 +523,19 @@ mod tests {
         assert!(output.contains("ANSI_COLOR=\"38;2;23;147;209\"\n"));
     }
 
+    #[test]
+    fn test_translations() {
+        let mut info = load_os_info(include_str!("../../../sample.json")).unwrap();
+        info.metadata
+            .identity
+            .translations
+            .insert("display", "de", "AerynOS 0.25.1 (Deutsch)");
+
+        // os-release defines no localized fields
+        let output = OsRelease::from(&info).to_string();
+        assert!(!output.contains("Deutsch"));
+    }
+
     #[test]
     fn test_extra_fields() {
         let mut release = OsRelease::new(
@@ -0,0 +1,799 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Static overview page generation
+//!
+//! Renders an "About" page from an [`OsInfo`] and its resolved
+//! [`TechnologyCapabilities`] as Markdown or HTML. The page layout is a
+//! template with `{{placeholder}}` markers, so sites can supply their own
+//! while reusing the generated sections. Placeholders are filled in a single
+//! pass, so document text that looks like a placeholder is never expanded.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::{CadenceType, MaintainerRole, OsInfo, TechnologyCapabilities, Url, WebsiteScope};
+
+/// Default Markdown page layout
+pub const DEFAULT_MARKDOWN_TEMPLATE: &str = "# About {{name}}
+
+{{identity}}
+{{links}}
+{{community}}
+{{maintainers}}
+{{features}}
+{{technologies}}";
+
+/// Default HTML page layout
+pub const DEFAULT_HTML_TEMPLATE: &str = "<!DOCTYPE html>
+<html lang=\"{{lang}}\">
+<head>
+<meta charset=\"utf-8\">
+<title>About {{name}}</title>
+</head>
+<body>
+<h1>About {{name}}</h1>
+{{identity}}
+{{links}}
+{{community}}
+{{maintainers}}
+{{features}}
+{{technologies}}
+</body>
+</html>
+";
+
+/// Output format of the overview page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageFormat {
+    /// CommonMark
+    Markdown,
+    /// HTML5
+    Html,
+}
+
+/// Renders an overview page for an OS
+#[derive(Debug)]
+pub struct OverviewPage<'a> {
+    info: &'a OsInfo,
+    technologies: &'a [TechnologyCapabilities],
+    format: PageFormat,
+    template: Option<String>,
+    language: String,
+}
+
+impl<'a> OverviewPage<'a> {
+    /// Creates a page using the default template for the format
+    pub fn new(
+        info: &'a OsInfo,
+        technologies: &'a [TechnologyCapabilities],
+        format: PageFormat,
+    ) -> Self {
+        Self {
+            info,
+            technologies,
+            format,
+            template: None,
+            language: "en".to_string(),
+        }
+    }
+
+    /// Sets the language of the page, `en` by default
+    ///
+    /// Names and descriptions use the document's translations for this
+    /// language where available, and HTML pages declare it in `lang`.
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = language.into();
+        self
+    }
+
+    /// Overrides the page template
+    ///
+    /// Available placeholders are `{{name}}`, `{{display}}`, `{{version}}`,
+    /// `{{lang}}`, `{{identity}}`, `{{maintainers}}`, `{{links}}`,
+    /// `{{community}}`, `{{features}}` and `{{technologies}}`. Unknown
+    /// placeholders are kept.
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    /// Renders the page
+    pub fn render(&self) -> String {
+        let template = self.template.as_deref().unwrap_or(match self.format {
+            PageFormat::Markdown => DEFAULT_MARKDOWN_TEMPLATE,
+            PageFormat::Html => DEFAULT_HTML_TEMPLATE,
+        });
+
+        let identity = &self.info.metadata.identity;
+        let locales = self.locales();
+        fill_template(template, |key| {
+            Some(match key {
+                "name" => self.text(identity.localized_name(locales)),
+                "display" => self.text(identity.localized_display(locales)),
+                "version" => self.text(&self.info.metadata.version.full),
+                "lang" => self.text(&self.language),
+                "identity" => self.section(self.identity_section()),
+                "maintainers" => self.section(self.maintainers_section()),
+                "links" => self.section(self.links_section()),
+                "community" => self.section(self.community_section()),
+                "features" => self.section(self.features_section()),
+                "technologies" => self.section(self.technologies_section()),
+                _ => return None,
+            })
+        })
+    }
+
+    fn locales(&self) -> &[String] {
+        std::slice::from_ref(&self.language)
+    }
+
+    fn identity_section(&self) -> Section {
+        let metadata = &self.info.metadata;
+        let version = &metadata.version;
+
+        let mut facts = vec![
+            fact("Identifier", [Inline::code(&metadata.identity.id)]),
+            fact(
+                "Version",
+                [Inline::text(match version.codename.as_ref() {
+                    Some(codename) => format!("{} ({codename})", version.full),
+                    None => version.full.clone(),
+                })],
+            ),
+            fact(
+                "Released",
+                [Inline::text(
+                    version.released.format("%Y-%m-%d").to_string(),
+                )],
+            ),
+            fact(
+                "Project started",
+                [Inline::text(
+                    self.info.start_date.format("%Y-%m-%d").to_string(),
+                )],
+            ),
+        ];
+        if let Some(id_like) = &metadata.identity.id_like {
+            facts.insert(1, fact("Based on", [Inline::text(id_like)]));
+        }
+        if let Some(url) = &version.announcement {
+            facts.push(vec![Inline::link("Release announcement", url)]);
+        }
+
+        let mut blocks = vec![Block::List(facts)];
+
+        let history = metadata
+            .identity
+            .former_identities
+            .iter()
+            .map(|former| {
+                let mut item = vec![
+                    Inline::strong(&former.name),
+                    Inline::text(format!(
+                        " ({}), {} to {}",
+                        former.id,
+                        former.start_date.format("%Y-%m-%d"),
+                        former.end_date.format("%Y-%m-%d"),
+                    )),
+                ];
+                if let Some(version) = &former.end_version {
+                    item.push(Inline::text(format!(", until version {version}")));
+                }
+                if let Some(url) = &former.announcement {
+                    item.push(Inline::text(" - "));
+                    item.push(Inline::link("announcement", url));
+                }
+                item
+            })
+            .collect::<Vec<_>>();
+        if !history.is_empty() {
+            blocks.push(Block::Heading("Former identities".to_string()));
+            blocks.push(Block::List(history));
+        }
+
+        Section {
+            title: metadata
+                .identity
+                .localized_display(self.locales())
+                .to_string(),
+            blocks,
+        }
+    }
+
+    fn maintainers_section(&self) -> Section {
+        let groups = self
+            .info
+            .metadata
+            .maintainers
+            .iter()
+            .collect::<BTreeMap<_, _>>();
+
+        let blocks = groups
+            .into_iter()
+            .flat_map(|(group, maintainers)| {
+                let items = maintainers
+                    .iter()
+                    .filter(|m| m.end_date.is_none())
+                    .map(|m| {
+                        vec![
+                            Inline::text(&m.name),
+                            Inline::text(format!(" - {}", role_title(&m.role))),
+                        ]
+                    })
+                    .collect();
+                [Block::Heading(group_title(group)), Block::List(items)]
+            })
+            .collect();
+
+        Section {
+            title: "Maintainers".to_string(),
+            blocks,
+        }
+    }
+
+    fn links_section(&self) -> Section {
+        let mut by_scope = BTreeMap::<_, Vec<_>>::new();
+        for site in self.info.resources.websites.values() {
+            by_scope.entry(site.scope).or_default().push(site);
+        }
+
+        let blocks = by_scope
+            .into_iter()
+            .flat_map(|(scope, sites)| {
+                let mut sites = sites
+                    .into_iter()
+                    .map(|site| (site.localized_display_name(self.locales()), &site.url))
+                    .collect::<Vec<_>>();
+                sites.sort();
+                let items = sites
+                    .into_iter()
+                    .map(|(name, url)| vec![Inline::link(name, url)])
+                    .collect();
+                [
+                    Block::Heading(scope_title(scope).to_string()),
+                    Block::List(items),
+                ]
+            })
+            .collect();
+
+        Section {
+            title: "Links".to_string(),
+            blocks,
+        }
+    }
+
+    fn community_section(&self) -> Section {
+        let resources = &self.info.resources;
+        let links = |entries: Vec<(&String, &Url, String)>| {
+            let mut entries = entries;
+            entries.sort();
+            entries
+                .into_iter()
+                .map(|(name, url, platform)| {
+                    vec![
+                        Inline::link(name, url),
+                        Inline::text(format!(" ({platform})")),
+                    ]
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let social = links(
+            resources
+                .social
+                .values()
+                .map(|l| {
+                    let handle = l.handle().unwrap_or_else(|| l.platform.to_string());
+                    (&l.display_name, &l.url, handle)
+                })
+                .collect(),
+        );
+        let funding = links(
+            resources
+                .funding
+                .values()
+                .map(|l| (&l.display_name, &l.url, l.platform.to_string()))
+                .collect(),
+        );
+
+        let mut blocks = vec![];
+        if !social.is_empty() {
+            blocks.push(Block::Heading("Social".to_string()));
+            blocks.push(Block::List(social));
+        }
+        if !funding.is_empty() {
+            blocks.push(Block::Heading("Support the project".to_string()));
+            blocks.push(Block::List(funding));
+        }
+
+        Section {
+            title: "Community".to_string(),
+            blocks,
+        }
+    }
+
+    fn features_section(&self) -> Section {
+        let system = &self.info.system;
+        let features = &system.features;
+        let firmware = &features.boot.firmware;
+        let yes_no = |b: bool| if b { "yes" } else { "no" };
+
+        let cadence = &system.update.cadence;
+        let mut update = format!(
+            "{}, {}",
+            system.update.strategy,
+            cadence_title(&cadence.cadence_type)
+        );
+        for detail in [
+            &cadence.sync_interval,
+            &cadence.sync_day,
+            &cadence.release_schedule,
+            &cadence.support_timeline,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let _ = write!(update, ", {detail}");
+        }
+
+        let facts = [
+            (
+                "Platform",
+                format!(
+                    "{} ({})",
+                    system.platform.architecture, system.platform.variant
+                ),
+            ),
+            (
+                "Kernel",
+                format!("{} ({})", system.kernel.name, system.kernel.kernel_type),
+            ),
+            ("Bootloader", features.boot.bootloader.clone()),
+            (
+                "Firmware",
+                format!(
+                    "UEFI: {}, Secure Boot: {}, BIOS: {}",
+                    yes_no(firmware.uefi),
+                    yes_no(firmware.secure_boot),
+                    yes_no(firmware.bios)
+                ),
+            ),
+            (
+                "Filesystems",
+                format!(
+                    "{} by default, supports {}",
+                    features.filesystem.default,
+                    features.filesystem.supported.join(", ")
+                ),
+            ),
+            (
+                "Atomic updates",
+                format!(
+                    "{}, rollback {}",
+                    features.atomic_updates.strategy,
+                    if features.atomic_updates.rollback_support {
+                        "supported"
+                    } else {
+                        "unsupported"
+                    }
+                ),
+            ),
+            ("Updates", update),
+        ];
+
+        Section {
+            title: "Platform".to_string(),
+            blocks: vec![Block::List(
+                facts
+                    .into_iter()
+                    .map(|(label, value)| fact(label, [Inline::text(value)]))
+                    .collect(),
+            )],
+        }
+    }
+
+    fn technologies_section(&self) -> Section {
+        let technology = &self.info.system.composition.technology;
+        let list = |names: &[String]| {
+            names
+                .iter()
+                .map(|name| {
+                    let mut item = vec![Inline::strong(name)];
+                    if let Some(tech) = self.technologies.iter().find(|t| &t.name == name) {
+                        item.push(Inline::text(format!(
+                            ": {}",
+                            tech.localized_description(self.locales())
+                        )));
+                        let mut links = tech.links.iter().collect::<Vec<_>>();
+                        links.sort_by_key(|(key, _)| *key);
+                        for (key, link) in links {
+                            item.push(Inline::text(" - "));
+                            item.push(Inline::link(key, &link.url));
+                        }
+                    }
+                    item
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut blocks = vec![];
+        for (title, names) in [
+            ("Core", &technology.core),
+            ("Optional", &technology.optional),
+        ] {
+            if !names.is_empty() {
+                blocks.push(Block::Heading(title.to_string()));
+                blocks.push(Block::List(list(names)));
+            }
+        }
+
+        Section {
+            title: "Technologies".to_string(),
+            blocks,
+        }
+    }
+
+    fn text(&self, s: &str) -> String {
+        match self.format {
+            PageFormat::Markdown => escape_markdown(s),
+            PageFormat::Html => escape_markup(s),
+        }
+    }
+
+    fn section(&self, section: Section) -> String {
+        if section.blocks.is_empty() {
+            return String::new();
+        }
+        match self.format {
+            PageFormat::Markdown => section.to_markdown(),
+            PageFormat::Html => section.to_html(),
+        }
+    }
+}
+
+/// A titled part of the page
+struct Section {
+    title: String,
+    blocks: Vec<Block>,
+}
+
+enum Block {
+    Heading(String),
+    List(Vec<Vec<Inline>>),
+}
+
+enum Inline {
+    Text(String),
+    Strong(String),
+    Code(String),
+    Link { text: String, url: String },
+}
+
+impl Inline {
+    fn text(s: impl Into<String>) -> Self {
+        Self::Text(s.into())
+    }
+
+    fn strong(s: impl Into<String>) -> Self {
+        Self::Strong(s.into())
+    }
+
+    fn code(s: impl Into<String>) -> Self {
+        Self::Code(s.into())
+    }
+
+    fn link(text: impl Into<String>, url: impl Into<String>) -> Self {
+        Self::Link {
+            text: text.into(),
+            url: url.into(),
+        }
+    }
+}
+
+/// A list item of the form `**Label:** value`
+fn fact(label: &str, value: impl IntoIterator<Item = Inline>) -> Vec<Inline> {
+    [Inline::strong(format!("{label}:")), Inline::text(" ")]
+        .into_iter()
+        .chain(value)
+        .collect()
+}
+
+impl Section {
+    fn to_markdown(&self) -> String {
+        let mut out = format!("## {}\n", escape_markdown(&self.title));
+        for block in &self.blocks {
+            match block {
+                Block::Heading(title) => {
+                    let _ = write!(out, "\n### {}\n", escape_markdown(title));
+                }
+                Block::List(items) => {
+                    out.push('\n');
+                    for item in items {
+                        let _ =
+                            writeln!(out, "- {}", item.iter().map(markdown).collect::<String>());
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    fn to_html(&self) -> String {
+        let mut out = format!("<section>\n<h2>{}</h2>\n", escape_markup(&self.title));
+        for block in &self.blocks {
+            match block {
+                Block::Heading(title) => {
+                    let _ = writeln!(out, "<h3>{}</h3>", escape_markup(title));
+                }
+                Block::List(items) => {
+                    out.push_str("<ul>\n");
+                    for item in items {
+                        let _ = writeln!(
+                            out,
+                            "<li>{}</li>",
+                            item.iter().map(html).collect::<String>()
+                        );
+                    }
+                    out.push_str("</ul>\n");
+                }
+            }
+        }
+        out.push_str("</section>\n");
+        out
+    }
+}
+
+fn markdown(inline: &Inline) -> String {
+    match inline {
+        Inline::Text(s) => escape_markdown(s),
+        Inline::Strong(s) => format!("**{}**", escape_markdown(s)),
+        Inline::Code(s) => {
+            // The fence must be longer than any backtick run in the code
+            let longest = s
+                .split(|c| c != '`')
+                .map(str::len)
+                .max()
+                .unwrap_or_default();
+            let fence = "`".repeat(longest + 1);
+            let pad = if s.starts_with('`') || s.ends_with('`') {
+                " "
+            } else {
+                ""
+            };
+            format!("{fence}{pad}{s}{pad}{fence}")
+        }
+        Inline::Link { text, url } => format!(
+            "[{}](<{}>)",
+            escape_markdown(text),
+            url.replace('<', "%3C").replace('>', "%3E")
+        ),
+    }
+}
+
+fn html(inline: &Inline) -> String {
+    match inline {
+        Inline::Text(s) => escape_markup(s),
+        Inline::Strong(s) => format!("<strong>{}</strong>", escape_markup(s)),
+        Inline::Code(s) => format!("<code>{}</code>", escape_markup(s)),
+        Inline::Link { text, url } => {
+            format!(
+                "<a href=\"{}\">{}</a>",
+                escape_markup(url),
+                escape_markup(text)
+            )
+        }
+    }
+}
+
+/// Escape text so CommonMark renders it literally
+fn escape_markdown(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~' | '&' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' | '\r' => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Fill `{{key}}` placeholders in a single pass
+///
+/// Keys `value` returns `None` for are left as written. Substituted text is
+/// never scanned again.
+pub(crate) fn fill_template(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after
+            .find("}}")
+            .and_then(|end| Some((end, value(&after[..end])?)))
+        {
+            Some((end, text)) => {
+                out.push_str(&text);
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Escape text for use in HTML or XML content and attribute values
+pub(crate) fn escape_markup(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn scope_title(scope: WebsiteScope) -> &'static str {
+    match scope {
+        WebsiteScope::Home => "Home",
+        WebsiteScope::Documentation => "Documentation",
+        WebsiteScope::Support => "Support",
+        WebsiteScope::BugTracker => "Bug tracker",
+        WebsiteScope::Developer => "Development",
+        WebsiteScope::Public => "News and updates",
+        WebsiteScope::EndUserDocs => "User guides",
+        WebsiteScope::DeveloperDocs => "Developer documentation",
+        WebsiteScope::PrivacyPolicy => "Privacy policy",
+        WebsiteScope::TermsOfService => "Terms of service",
+        WebsiteScope::Legal => "Legal",
+        WebsiteScope::SecurityPolicy => "Security policy",
+    }
+}
+
+fn role_title(role: &MaintainerRole) -> &'static str {
+    match role {
+        MaintainerRole::Founder => "Founder",
+        MaintainerRole::Steward => "Steward",
+        MaintainerRole::Maintainer => "Maintainer",
+        MaintainerRole::Contributor => "Contributor",
+    }
+}
+
+fn cadence_title(cadence: &CadenceType) -> &'static str {
+    match cadence {
+        CadenceType::Rolling => "rolling release",
+        CadenceType::Fixed => "fixed release",
+        CadenceType::Lts => "long-term support release",
+        CadenceType::Point => "point release",
+    }
+}
+
+/// Turn a group key such as `core_team` into `Core team`
+fn group_title(group: &str) -> String {
+    let words = group.replace(['_', '-'], " ");
+    let mut chars = words.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_os_info, load_technology};
+
+    fn fixtures() -> (OsInfo, Vec<TechnologyCapabilities>) {
+        let info = load_os_info(include_str!("../../../os/aerynos.json")).unwrap();
+        let technologies = [
+            include_str!("../../../technologies/moss.json"),
+            include_str!("../../../technologies/boulder.json"),
+            include_str!("../../../technologies/flatpak.json"),
+        ]
+        .into_iter()
+        .map(|t| load_technology(t).unwrap())
+        .collect();
+        (info, technologies)
+    }
+
+    #[test]
+    fn test_markdown() {
+        let (info, technologies) = fixtures();
+        let page = OverviewPage::new(&info, &technologies, PageFormat::Markdown).render();
+
+        assert!(page.starts_with("# About AerynOS\n"));
+        assert!(page.contains("### Former identities\n\n- **Serpent OS** (serpentos), 2020-06-15 to 2025-03-17, until version 0.24.6"));
+        assert!(page.contains("### Core team\n\n- Core Team - Maintainer\n"));
+        assert!(page.contains(
+            "### Bug tracker\n\n- [Issue Tracker](<https://github.com/AerynOS/issues>)\n"
+        ));
+        assert!(page.contains(
+            "- [Matrix Chat Room](<https://matrix.to/#/#aerynos:matrix.org>) (\\#aerynos:matrix.org)\n"
+        ));
+        assert!(page.contains("- **Filesystems:** xfs by default, supports ext4, f2fs, xfs\n"));
+        assert!(page.contains("- **moss**: Modern package management and system composition tool"));
+
+        // Scopes are listed in declaration order
+        assert!(page.find("### Home") < page.find("### Documentation"));
+    }
+
+    #[test]
+    fn test_html_escaping() {
+        let (mut info, technologies) = fixtures();
+        info.metadata.identity.name = "Aeryn <OS>".to_string();
+        let page = OverviewPage::new(&info, &technologies, PageFormat::Html).render();
+
+        assert!(page.starts_with("<!DOCTYPE html>\n<html lang=\"en\">\n"));
+        assert!(page.contains("<title>About Aeryn &lt;OS&gt;</title>"));
+        assert!(page.contains("<li><strong>Bootloader:</strong> systemd-boot</li>"));
+        assert!(!page.contains("{{"));
+    }
+
+    #[test]
+    fn test_custom_template() {
+        let (info, technologies) = fixtures();
+        let page = OverviewPage::new(&info, &technologies, PageFormat::Markdown)
+            .with_template("{{display}} / {{version}}\n{{unknown}}")
+            .render();
+
+        assert_eq!(page, "AerynOS 0.25.1 / 0.2025.1\n{{unknown}}");
+    }
+
+    #[test]
+    fn test_markdown_escaping() {
+        let (mut info, technologies) = fixtures();
+        info.metadata.identity.name = "*Aeryn* [OS]".to_string();
+        info.metadata.identity.id = "a`b".to_string();
+        let page = OverviewPage::new(&info, &technologies, PageFormat::Markdown).render();
+
+        assert!(page.starts_with("# About \\*Aeryn\\* \\[OS\\]\n"));
+        assert!(page.contains("- **Identifier:** ``a`b``\n"));
+    }
+
+    #[test]
+    fn test_single_pass() {
+        let (mut info, technologies) = fixtures();
+        info.metadata.identity.display = "{{version}}".to_string();
+        let page = OverviewPage::new(&info, &technologies, PageFormat::Html)
+            .with_template("{{display}} {{")
+            .render();
+
+        assert_eq!(page, "{{version}} {{");
+    }
+
+    #[test]
+    fn test_language() {
+        let (mut info, technologies) = fixtures();
+        info.metadata
+            .identity
+            .translations
+            .insert("display", "de", "AerynOS (Deutsch)");
+        let page = OverviewPage::new(&info, &technologies, PageFormat::Html)
+            .with_language("de-DE")
+            .render();
+
+        assert!(page.contains("<html lang=\"de-DE\">"));
+        assert!(page.contains("<h2>AerynOS (Deutsch)</h2>"));
+    }
+
+    #[test]
+    fn test_group_title() {
+        assert_eq!(group_title("core_team"), "Core team");
+        assert_eq!(group_title("founders"), "Founders");
+        assert_eq!(group_title(""), "");
+    }
+}
@@ -0,0 +1,523 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Typed handling of the PGP key in a security contact
+//!
+//! Documents store the key as a single string, which may be an inline ASCII
+//! armoured key block, a fingerprint, a URL to fetch the key from, or an
+//! address to look up via the Web Key Directory (WKD). The string is kept as
+//! written, so a document round-trips unchanged even when the key reference
+//! isn't in a form this crate understands.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest as _, Sha256};
+use snafu::{ResultExt as _, Snafu, ensure};
+
+const ARMOR_BEGIN: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----";
+const ARMOR_END: &str = "-----END PGP PUBLIC KEY BLOCK-----";
+const FINGERPRINT_URI: &str = "openpgp4fpr:";
+const WKD_URI: &str = "wkd:";
+
+/// Errors raised while parsing a PGP key reference
+#[derive(Debug, Snafu)]
+pub enum PgpKeyError {
+    #[snafu(display("armoured key must end with `{ARMOR_END}`"))]
+    MissingArmorEnd,
+
+    #[snafu(display("armoured key block is empty"))]
+    EmptyArmor,
+
+    #[snafu(display("armoured key block is not valid base64"))]
+    ArmorBase64 { source: base64::DecodeError },
+
+    #[snafu(display("armoured key block checksum doesn't match its contents"))]
+    ArmorChecksum,
+
+    #[snafu(display("fingerprint has {length} hex digits, expected 40 (v4) or 64 (v6)"))]
+    FingerprintLength { length: usize },
+
+    #[snafu(display("fingerprint contains non-hex character `{character}`"))]
+    FingerprintCharacter { character: char },
+
+    #[snafu(display("invalid WKD address `{address}`"))]
+    WkdAddress { address: String },
+
+    #[snafu(display("unrecognised PGP key format"))]
+    Unrecognised,
+}
+
+/// Reference to the PGP key used for encrypted vulnerability reports
+///
+/// Parsing with [`FromStr`] is strict, while documents accept any string and
+/// keep unrecognised ones as [`PgpKeyKind::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct PgpKey {
+    raw: String,
+    kind: PgpKeyKind,
+}
+
+/// What a [`PgpKey`] reference points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgpKeyKind {
+    /// Inline ASCII armoured public key block
+    Armored(String),
+    /// Key fingerprint
+    Fingerprint(Fingerprint),
+    /// URL the key can be downloaded from
+    Url(String),
+    /// Email address to look the key up with WKD
+    Wkd(String),
+    /// Any other reference, kept verbatim
+    Other(String),
+}
+
+impl PgpKey {
+    /// The reference as written
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Interpretation of the reference
+    pub fn kind(&self) -> &PgpKeyKind {
+        &self.kind
+    }
+
+    /// Returns the key fingerprint when it is known or can be derived
+    ///
+    /// For armoured keys this is computed from the primary key packet.
+    pub fn fingerprint(&self) -> Option<Fingerprint> {
+        match &self.kind {
+            PgpKeyKind::Fingerprint(fingerprint) => Some(fingerprint.clone()),
+            PgpKeyKind::Armored(armor) => armored_fingerprint(armor),
+            PgpKeyKind::Url(_) | PgpKeyKind::Wkd(_) | PgpKeyKind::Other(_) => None,
+        }
+    }
+}
+
+impl FromStr for PgpKey {
+    type Err = PgpKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            raw: s.to_string(),
+            kind: s.parse()?,
+        })
+    }
+}
+
+impl FromStr for PgpKeyKind {
+    type Err = PgpKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if s.starts_with(ARMOR_BEGIN) {
+            ensure!(s.ends_with(ARMOR_END), MissingArmorEndSnafu);
+            decode_armor(s)?;
+            return Ok(PgpKeyKind::Armored(s.to_string()));
+        }
+
+        if s.starts_with("https://") || s.starts_with("http://") {
+            return Ok(PgpKeyKind::Url(s.to_string()));
+        }
+
+        if let Some(fingerprint) = s.strip_prefix(FINGERPRINT_URI) {
+            return fingerprint.parse().map(PgpKeyKind::Fingerprint);
+        }
+
+        if let Some(address) = s.strip_prefix(WKD_URI) {
+            ensure!(
+                is_address(address),
+                WkdAddressSnafu {
+                    address: address.to_string()
+                }
+            );
+            return Ok(PgpKeyKind::Wkd(address.to_string()));
+        }
+
+        if is_address(s) {
+            return Ok(PgpKeyKind::Wkd(s.to_string()));
+        }
+
+        if s.trim_start_matches("0x")
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() || c.is_whitespace())
+        {
+            return s.parse().map(PgpKeyKind::Fingerprint);
+        }
+
+        Err(PgpKeyError::Unrecognised)
+    }
+}
+
+impl Display for PgpKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl From<String> for PgpKey {
+    fn from(raw: String) -> Self {
+        let kind = raw
+            .parse()
+            .unwrap_or_else(|_| PgpKeyKind::Other(raw.clone()));
+        Self { raw, kind }
+    }
+}
+
+impl From<PgpKey> for String {
+    fn from(key: PgpKey) -> Self {
+        key.raw
+    }
+}
+
+/// An OpenPGP key fingerprint, stored as upper case hex
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fingerprint(String);
+
+impl Fingerprint {
+    /// Hex digits of the fingerprint
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// OpenPGP key version implied by the fingerprint length
+    pub fn version(&self) -> u8 {
+        if self.0.len() == 64 { 6 } else { 4 }
+    }
+
+    /// Human readable form, in groups of four digits
+    pub fn grouped(&self) -> String {
+        self.0
+            .as_bytes()
+            .chunks(4)
+            .map(|chunk| std::str::from_utf8(chunk).expect("hex is ascii"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self(bytes.iter().map(|b| format!("{b:02X}")).collect())
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = PgpKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s
+            .trim()
+            .trim_start_matches("0x")
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+
+        if let Some(character) = hex.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return FingerprintCharacterSnafu { character }.fail();
+        }
+        ensure!(
+            hex.len() == 40 || hex.len() == 64,
+            FingerprintLengthSnafu { length: hex.len() }
+        );
+
+        Ok(Self(hex.to_ascii_uppercase()))
+    }
+}
+
+impl Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Loose check that an address is `local@domain.tld`
+fn is_address(s: &str) -> bool {
+    s.split_once('@').is_some_and(|(local, domain)| {
+        !local.is_empty()
+            && domain.contains('.')
+            && !s.contains(char::is_whitespace)
+            && !domain.contains('@')
+    })
+}
+
+/// Decode the body of an armoured block, checking its CRC24 checksum
+///
+/// The `=XXXX` checksum line is optional since RFC 9580, but must match when
+/// present.
+fn decode_armor(armor: &str) -> Result<Vec<u8>, PgpKeyError> {
+    let (body, checksum) = armor_parts(armor);
+    ensure!(!body.is_empty(), EmptyArmorSnafu);
+    let data = BASE64.decode(body).context(ArmorBase64Snafu)?;
+
+    if let Some(checksum) = checksum {
+        let expected = BASE64.decode(checksum).context(ArmorBase64Snafu)?;
+        ensure!(
+            expected == crc24(&data).to_be_bytes()[1..],
+            ArmorChecksumSnafu
+        );
+    }
+
+    Ok(data)
+}
+
+/// OpenPGP armour checksum, RFC 9580 section 6.1.1
+fn crc24(data: &[u8]) -> u32 {
+    const INIT: u32 = 0x00B7_04CE;
+    const POLY: u32 = 0x0186_4CFB;
+
+    let mut crc = INIT;
+    for &byte in data {
+        crc ^= u32::from(byte) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x0100_0000 != 0 {
+                crc ^= POLY;
+            }
+        }
+    }
+    crc & 0x00FF_FFFF
+}
+
+/// Base64 body and checksum of an armoured block, without headers
+fn armor_parts(armor: &str) -> (String, Option<&str>) {
+    let inner = armor
+        .trim()
+        .trim_start_matches(ARMOR_BEGIN)
+        .trim_end_matches(ARMOR_END);
+    let inner = inner
+        .strip_prefix("\r\n")
+        .or_else(|| inner.strip_prefix('\n'))
+        .unwrap_or(inner);
+    let lines = inner.lines().map(str::trim).collect::<Vec<_>>();
+
+    // Optional `Key: value` headers are terminated by an empty line
+    let body = match lines.iter().position(|l| l.is_empty()) {
+        Some(blank) if lines[..blank].iter().all(|l| l.contains(": ")) => &lines[blank + 1..],
+        _ => &lines[..],
+    };
+
+    let checksum = body
+        .iter()
+        .rev()
+        .find(|l| !l.is_empty())
+        .and_then(|l| l.strip_prefix('='));
+    let data = body
+        .iter()
+        .filter(|l| !l.is_empty() && !l.starts_with('='))
+        .copied()
+        .collect();
+    (data, checksum)
+}
+
+/// Compute the fingerprint of the primary key in an armoured block
+fn armored_fingerprint(armor: &str) -> Option<Fingerprint> {
+    let data = decode_armor(armor).ok()?;
+    let (tag, body) = first_packet(&data)?;
+    // Public key packet
+    if tag != 6 {
+        return None;
+    }
+
+    match body.first()? {
+        4 => {
+            let mut hasher = Sha1::new();
+            hasher.update([0x99]);
+            hasher.update(u16::try_from(body.len()).ok()?.to_be_bytes());
+            hasher.update(body);
+            Some(Fingerprint::from_bytes(&hasher.finalize()))
+        }
+        6 => {
+            let mut hasher = Sha256::new();
+            hasher.update([0x9b]);
+            hasher.update(u32::try_from(body.len()).ok()?.to_be_bytes());
+            hasher.update(body);
+            Some(Fingerprint::from_bytes(&hasher.finalize()))
+        }
+        _ => None,
+    }
+}
+
+/// Split the first OpenPGP packet into its tag and body
+fn first_packet(data: &[u8]) -> Option<(u8, &[u8])> {
+    let header = *data.first()?;
+    if header & 0x80 == 0 {
+        return None;
+    }
+
+    let (tag, len, offset) = if header & 0x40 != 0 {
+        // New format length
+        let tag = header & 0x3f;
+        match *data.get(1)? {
+            n @ 0..192 => (tag, usize::from(n), 2),
+            n @ 192..224 => {
+                let len = (usize::from(n - 192) << 8) + usize::from(*data.get(2)?) + 192;
+                (tag, len, 3)
+            }
+            255 => (tag, be_len(data.get(2..6)?), 6),
+            _ => return None,
+        }
+    } else {
+        // Old format length
+        let tag = (header >> 2) & 0x0f;
+        match header & 0x03 {
+            0 => (tag, usize::from(*data.get(1)?), 2),
+            1 => (tag, be_len(data.get(1..3)?), 3),
+            2 => (tag, be_len(data.get(1..5)?), 5),
+            _ => return None,
+        }
+    };
+
+    Some((tag, data.get(offset..offset + len)?))
+}
+
+fn be_len(bytes: &[u8]) -> usize {
+    bytes.iter().fold(0, |acc, &b| (acc << 8) | usize::from(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Ed25519 v4 key generated with `gpg --quick-gen-key`
+    const ARMORED: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----
+Comment: test key
+
+mDMEatVR2BYJKwYBBAHaRw8BAQdArc2YHCGxvty04jrsObD8ObwRFGxbvLtGOLkM
+sU2uEC20F1Rlc3QgPHRlc3RAZXhhbXBsZS5jb20+iJAEExYIADgWIQRQpt32ypnX
+nPSEUSIN1HNAhd8sEwUCatVR2AIbAwULCQgHAgYVCgkICwIEFgIDAQIeAQIXgAAK
+CRAN1HNAhd8sEynnAP97OhtSvlfJL5yVgcMUAuBz/l5xI5V+FBpOOOWrnaKtQAD/
+eWoXwB6JAuMW0D4PNnQdStkoe+/z8KzpBsRySGK5XQw=
+=NcdI
+-----END PGP PUBLIC KEY BLOCK-----";
+
+    #[test]
+    fn test_parse_variants() {
+        assert!(matches!(ARMORED.parse(), Ok(PgpKeyKind::Armored(_))));
+        assert_eq!(
+            "https://example.com/key.asc".parse::<PgpKeyKind>().unwrap(),
+            PgpKeyKind::Url("https://example.com/key.asc".to_string())
+        );
+        assert_eq!(
+            "security@example.com".parse::<PgpKeyKind>().unwrap(),
+            PgpKeyKind::Wkd("security@example.com".to_string())
+        );
+        assert_eq!(
+            "wkd:security@example.com".parse::<PgpKeyKind>().unwrap(),
+            PgpKeyKind::Wkd("security@example.com".to_string())
+        );
+
+        let key = "aaaa bbbb cccc dddd eeee ffff 0000 1111 2222 3333"
+            .parse::<PgpKey>()
+            .unwrap();
+        let PgpKeyKind::Fingerprint(fingerprint) = key.kind() else {
+            panic!("expected fingerprint");
+        };
+        assert_eq!(
+            fingerprint.as_str(),
+            "AAAABBBBCCCCDDDDEEEEFFFF0000111122223333"
+        );
+        assert_eq!(
+            fingerprint.grouped(),
+            "AAAA BBBB CCCC DDDD EEEE FFFF 0000 1111 2222 3333"
+        );
+        assert_eq!(fingerprint.version(), 4);
+        // Written form is kept
+        assert_eq!(
+            key.to_string(),
+            "aaaa bbbb cccc dddd eeee ffff 0000 1111 2222 3333"
+        );
+        assert_eq!(key.to_string().parse::<PgpKey>().unwrap(), key);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            "-----BEGIN PGP PUBLIC KEY BLOCK-----\nabc".parse::<PgpKey>(),
+            Err(PgpKeyError::MissingArmorEnd)
+        ));
+        assert!(matches!(
+            format!("{ARMOR_BEGIN}\n\n{ARMOR_END}").parse::<PgpKey>(),
+            Err(PgpKeyError::EmptyArmor)
+        ));
+        assert!(matches!(
+            "openpgp4fpr:ABCD".parse::<PgpKey>(),
+            Err(PgpKeyError::FingerprintLength { length: 4 })
+        ));
+        assert!(matches!(
+            "openpgp4fpr:ZZZZ".parse::<PgpKey>(),
+            Err(PgpKeyError::FingerprintCharacter { character: 'Z' })
+        ));
+        assert!(matches!(
+            "wkd:nobody".parse::<PgpKey>(),
+            Err(PgpKeyError::WkdAddress { .. })
+        ));
+        assert!(matches!(
+            "my key".parse::<PgpKey>(),
+            Err(PgpKeyError::Unrecognised)
+        ));
+    }
+
+    #[test]
+    fn test_armored_fingerprint() {
+        let key = ARMORED.parse::<PgpKey>().unwrap();
+        let fingerprint = key.fingerprint().unwrap();
+        assert_eq!(fingerprint.version(), 4);
+        assert_eq!(
+            fingerprint.as_str(),
+            "50A6DDF6CA99D79CF48451220DD4734085DF2C13"
+        );
+
+        // Without armour headers
+        let plain = ARMORED.replace("Comment: test key\n", "");
+        assert_eq!(
+            plain.parse::<PgpKey>().unwrap().fingerprint(),
+            Some(fingerprint.clone())
+        );
+
+        // Framing alone isn't enough, the body must be a valid key
+        assert!(matches!(
+            format!("{ARMOR_BEGIN}\nSample Key\n{ARMOR_END}").parse::<PgpKey>(),
+            Err(PgpKeyError::ArmorBase64 { .. })
+        ));
+        assert!(matches!(
+            ARMORED.replace("=NcdI", "=AAAA").parse::<PgpKey>(),
+            Err(PgpKeyError::ArmorChecksum)
+        ));
+        assert!(matches!(
+            ARMORED.replace("mDMEatVR2", "mDMEatVR3").parse::<PgpKey>(),
+            Err(PgpKeyError::ArmorChecksum)
+        ));
+
+        // The checksum is optional
+        let unchecked = ARMORED.replace("=NcdI\n", "").parse::<PgpKey>().unwrap();
+        assert_eq!(unchecked.fingerprint(), Some(fingerprint));
+    }
+
+    #[test]
+    fn test_serde() {
+        for raw in [
+            "https://example.com/key.asc",
+            "security@example.com",
+            "AAAA BBBB CCCC DDDD EEEE FFFF 0000 1111 2222 3333",
+            "0xaaaabbbbccccddddeeeeffff0000111122223333",
+        ] {
+            let json = serde_json::to_string(raw).unwrap();
+            let key: PgpKey = serde_json::from_str(&json).unwrap();
+            assert!(!matches!(key.kind(), PgpKeyKind::Other(_)));
+            assert_eq!(serde_json::to_string(&key).unwrap(), json);
+        }
+
+        // Opaque references don't fail the document
+        let key: PgpKey = serde_json::from_str("\"my key\"").unwrap();
+        assert_eq!(key.kind(), &PgpKeyKind::Other("my key".to_string()));
+        assert_eq!(serde_json::to_string(&key).unwrap(), "\"my key\"");
+    }
+}
@@ -0,0 +1,522 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Probing the running system
+//!
+//! An os-info document declares what the OS supports. This module reads what
+//! is actually in effect on the machine, from `/sys/firmware/efi`, the
+//! Secure Boot and boot loader interface EFI variables, `/proc/mounts` and
+//! the kernel's `uname` values in `/proc/sys/kernel`, and compares the two.
+//!
+//! All paths are resolved below a root, so probing can be tested against a
+//! fake sysfs/procfs tree.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::OsInfo;
+
+/// EFI firmware directory, relative to the root
+pub const EFI_DIR: &str = "sys/firmware/efi";
+/// EFI variables directory, relative to the root
+pub const EFIVARS_DIR: &str = "sys/firmware/efi/efivars";
+/// Mount table, relative to the root
+pub const MOUNTS_PATH: &str = "proc/mounts";
+/// Kernel identification directory, relative to the root
+pub const KERNEL_SYSCTL_DIR: &str = "proc/sys/kernel";
+
+/// Vendor GUID of the global EFI variables, such as `SecureBoot`
+const EFI_GLOBAL_GUID: &str = "8be4df61-93ca-11d2-aa0d-00e098032b8c";
+/// Vendor GUID of the boot loader interface variables
+const LOADER_GUID: &str = "4a67b082-0a4c-41cf-b6c7-440b29bb8c4f";
+
+/// Probes a system below a filesystem root
+#[derive(Debug, Clone)]
+pub struct Probe {
+    root: PathBuf,
+}
+
+impl Default for Probe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Probe {
+    /// Creates a probe for the running system
+    pub fn new() -> Self {
+        Self::with_root("/")
+    }
+
+    /// Creates a probe that reads sysfs and procfs below `root`
+    pub fn with_root<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns the root all paths are resolved against
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Reads the current state of the system
+    ///
+    /// Values that can't be read, such as EFI variables on a BIOS system or
+    /// without permission, are left unset.
+    pub fn probe(&self) -> ProbedSystem {
+        let uefi = self.root.join(EFI_DIR).is_dir();
+
+        ProbedSystem {
+            uefi,
+            secure_boot: uefi
+                .then(|| self.efivar("SecureBoot", EFI_GLOBAL_GUID))
+                .flatten()
+                .and_then(|data| data.first().map(|enabled| *enabled == 1)),
+            bootloader: uefi
+                .then(|| self.efivar("LoaderInfo", LOADER_GUID))
+                .flatten()
+                .and_then(|data| utf16_string(&data)),
+            root_filesystem: self.root_filesystem(),
+            kernel_name: self.kernel_value("ostype"),
+            kernel_release: self.kernel_value("osrelease"),
+            architecture: self.kernel_value("arch").or_else(|| {
+                (self.root == Path::new("/")).then(|| std::env::consts::ARCH.to_string())
+            }),
+        }
+    }
+
+    /// Reads an EFI variable, without its leading attribute bytes
+    pub(crate) fn efivar(&self, name: &str, guid: &str) -> Option<Vec<u8>> {
+        let data = fs::read(self.root.join(EFIVARS_DIR).join(format!("{name}-{guid}"))).ok()?;
+        data.get(4..).map(<[u8]>::to_vec)
+    }
+
+    /// Filesystem type mounted at `/`, the last mount wins
+    pub(crate) fn root_filesystem(&self) -> Option<String> {
+        let mounts = fs::read_to_string(self.root.join(MOUNTS_PATH)).ok()?;
+        mounts
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let _source = fields.next()?;
+                let target = fields.next()?;
+                let fstype = fields.next()?;
+                (target == "/").then(|| fstype.to_string())
+            })
+            .next_back()
+    }
+
+    /// A value from `/proc/sys/kernel`, as reported by `uname`
+    pub(crate) fn kernel_value(&self, name: &str) -> Option<String> {
+        let value = fs::read_to_string(self.root.join(KERNEL_SYSCTL_DIR).join(name)).ok()?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+}
+
+/// Decodes a NUL terminated UTF-16LE EFI string
+fn utf16_string(data: &[u8]) -> Option<String> {
+    let units = data
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|unit| *unit != 0)
+        .collect::<Vec<_>>();
+    let value = String::from_utf16(&units).ok()?;
+    (!value.is_empty()).then_some(value)
+}
+
+/// What was found on the running system
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProbedSystem {
+    /// Booted via UEFI
+    pub uefi: bool,
+    /// Secure Boot state, if the variable could be read
+    pub secure_boot: Option<bool>,
+    /// Boot loader identification from `LoaderInfo`, e.g. `systemd-boot 257.1`
+    pub bootloader: Option<String>,
+    /// Filesystem type of `/`
+    pub root_filesystem: Option<String>,
+    /// Kernel name, e.g. `Linux`
+    pub kernel_name: Option<String>,
+    /// Kernel release, e.g. `6.12.4-1`
+    pub kernel_release: Option<String>,
+    /// Machine architecture, e.g. `x86_64`
+    pub architecture: Option<String>,
+}
+
+/// Whether a declared feature is in effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureStatus {
+    /// The system matches the declaration
+    InEffect,
+    /// The system contradicts the declaration
+    NotInEffect,
+    /// The system state could not be determined
+    Unknown,
+}
+
+/// Result of checking one declared feature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureCheck {
+    /// Dotted path of the feature, e.g. `features.boot.firmware.uefi`
+    pub feature: &'static str,
+    /// Declared value
+    pub declared: String,
+    /// Observed value, if known
+    pub observed: Option<String>,
+    /// Outcome of the comparison
+    pub status: FeatureStatus,
+}
+
+/// Declared features compared against a probed system
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeatureReport {
+    /// One entry per declared feature
+    pub checks: Vec<FeatureCheck>,
+}
+
+impl FeatureReport {
+    /// Returns the check for a feature
+    pub fn get(&self, feature: &str) -> Option<&FeatureCheck> {
+        self.checks.iter().find(|check| check.feature == feature)
+    }
+
+    /// Features that are in effect
+    pub fn in_effect(&self) -> impl Iterator<Item = &FeatureCheck> {
+        self.with_status(FeatureStatus::InEffect)
+    }
+
+    /// Features the system contradicts
+    pub fn not_in_effect(&self) -> impl Iterator<Item = &FeatureCheck> {
+        self.with_status(FeatureStatus::NotInEffect)
+    }
+
+    fn with_status(&self, status: FeatureStatus) -> impl Iterator<Item = &FeatureCheck> {
+        self.checks
+            .iter()
+            .filter(move |check| check.status == status)
+    }
+}
+
+impl ProbedSystem {
+    /// Compares the features declared by `info` with this system
+    ///
+    /// Only supported features are checked: firmware modes declared `false`
+    /// and empty strings are skipped.
+    pub fn check(&self, info: &OsInfo) -> FeatureReport {
+        let system = &info.system;
+        let boot = &system.features.boot;
+        let firmware = &boot.firmware;
+        let mut checks = vec![];
+
+        let mut flag = |feature, declared: bool, observed: Option<bool>| {
+            if declared {
+                checks.push(FeatureCheck {
+                    feature,
+                    declared: true.to_string(),
+                    observed: observed.map(|v| v.to_string()),
+                    status: match observed {
+                        Some(true) => FeatureStatus::InEffect,
+                        Some(false) => FeatureStatus::NotInEffect,
+                        None => FeatureStatus::Unknown,
+                    },
+                });
+            }
+        };
+        flag(
+            "features.boot.firmware.uefi",
+            firmware.uefi,
+            Some(self.uefi),
+        );
+        flag(
+            "features.boot.firmware.bios",
+            firmware.bios,
+            Some(!self.uefi),
+        );
+        flag(
+            "features.boot.firmware.secure_boot",
+            firmware.secure_boot,
+            self.secure_boot,
+        );
+
+        let mut value =
+            |feature, declared: &str, observed: Option<&String>, same: fn(&str, &str) -> bool| {
+                if declared.is_empty() {
+                    return;
+                }
+                checks.push(FeatureCheck {
+                    feature,
+                    declared: declared.to_string(),
+                    observed: observed.cloned(),
+                    status: match observed {
+                        Some(observed) if same(declared, observed) => FeatureStatus::InEffect,
+                        Some(_) => FeatureStatus::NotInEffect,
+                        None => FeatureStatus::Unknown,
+                    },
+                });
+            };
+        value(
+            "features.boot.bootloader",
+            &boot.bootloader,
+            self.bootloader.as_ref(),
+            |declared, observed| {
+                let name = observed.split_whitespace().next().unwrap_or_default();
+                name.eq_ignore_ascii_case(declared)
+            },
+        );
+        value(
+            "features.filesystem.default",
+            &system.features.filesystem.default,
+            self.root_filesystem.as_ref(),
+            |declared, observed| declared.eq_ignore_ascii_case(observed),
+        );
+        value(
+            "kernel.name",
+            &system.kernel.name,
+            self.kernel_name.as_ref(),
+            |declared, observed| declared.eq_ignore_ascii_case(observed),
+        );
+        value(
+            "platform.architecture",
+            &system.platform.architecture,
+            self.architecture.as_ref(),
+            |declared, observed| normalize_arch(declared) == normalize_arch(observed),
+        );
+
+        FeatureReport { checks }
+    }
+}
+
+/// Kernel name, systemd name and other common spellings of each architecture
+///
+/// systemd names follow the `ARCHITECTURE=` values of os-release(5).
+const ARCHITECTURES: &[(&str, &str, &[&str])] = &[
+    ("x86_64", "x86-64", &["amd64"]),
+    ("x86", "x86", &["i386", "i486", "i586", "i686"]),
+    ("aarch64", "arm64", &[]),
+    ("aarch64_be", "arm64-be", &[]),
+    (
+        "arm",
+        "arm",
+        &["armv5tel", "armv6l", "armv7l", "armv7hl", "armhf", "armel"],
+    ),
+    ("armeb", "arm-be", &["armv7b"]),
+    ("alpha", "alpha", &[]),
+    ("arc", "arc", &[]),
+    ("arceb", "arc-be", &[]),
+    ("cris", "cris", &["crisv32"]),
+    ("ia64", "ia64", &[]),
+    ("loongarch64", "loongarch64", &[]),
+    ("m68k", "m68k", &[]),
+    ("mips", "mips", &[]),
+    ("mipsel", "mips-le", &[]),
+    ("mips64", "mips64", &[]),
+    ("mips64el", "mips64-le", &[]),
+    ("nios2", "nios2", &[]),
+    ("parisc", "parisc", &[]),
+    ("parisc64", "parisc64", &[]),
+    ("ppc", "ppc", &["powerpc"]),
+    ("ppcle", "ppc-le", &["powerpcle"]),
+    ("ppc64", "ppc64", &["powerpc64"]),
+    ("ppc64le", "ppc64-le", &["ppc64el", "powerpc64le"]),
+    ("riscv32", "riscv32", &[]),
+    ("riscv64", "riscv64", &[]),
+    ("s390", "s390", &[]),
+    ("s390x", "s390x", &[]),
+    ("sh", "sh", &["sh4"]),
+    ("sh64", "sh64", &[]),
+    ("sparc", "sparc", &[]),
+    ("sparc64", "sparc64", &[]),
+    ("tilegx", "tilegx", &[]),
+];
+
+/// Maps distribution and systemd architecture names onto kernel ones
+pub(crate) fn normalize_arch(arch: &str) -> &str {
+    ARCHITECTURES
+        .iter()
+        .find(|(kernel, systemd, aliases)| {
+            arch == *kernel || arch == *systemd || aliases.contains(&arch)
+        })
+        .map_or(arch, |(kernel, _, _)| kernel)
+}
+
+/// Maps any known architecture name onto the one systemd uses
+pub(crate) fn systemd_arch(arch: &str) -> &str {
+    let kernel = normalize_arch(arch);
+    ARCHITECTURES
+        .iter()
+        .find(|(name, _, _)| *name == kernel)
+        .map_or(arch, |(_, systemd, _)| systemd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_os_info;
+
+    fn tree(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("os-info-probe-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(KERNEL_SYSCTL_DIR)).unwrap();
+        fs::write(root.join(KERNEL_SYSCTL_DIR).join("ostype"), "Linux\n").unwrap();
+        fs::write(root.join(KERNEL_SYSCTL_DIR).join("osrelease"), "6.12.4-1\n").unwrap();
+        fs::write(root.join(KERNEL_SYSCTL_DIR).join("arch"), "x86_64\n").unwrap();
+        fs::write(
+            root.join(MOUNTS_PATH),
+            "proc /proc proc rw 0 0\n/dev/sda2 / ext4 rw 0 0\n/dev/sda1 /boot vfat rw 0 0\n/dev/sda3 / xfs rw 0 0\n",
+        )
+        .unwrap();
+        root
+    }
+
+    fn efivar(root: &Path, name: &str, guid: &str, value: &[u8]) {
+        let dir = root.join(EFIVARS_DIR);
+        fs::create_dir_all(&dir).unwrap();
+        let mut data = vec![0x06, 0, 0, 0];
+        data.extend_from_slice(value);
+        fs::write(dir.join(format!("{name}-{guid}")), data).unwrap();
+    }
+
+    fn utf16(s: &str) -> Vec<u8> {
+        s.encode_utf16()
+            .chain([0])
+            .flat_map(u16::to_le_bytes)
+            .collect()
+    }
+
+    #[test]
+    fn test_probe_uefi() {
+        let root = tree("uefi");
+        efivar(&root, "SecureBoot", EFI_GLOBAL_GUID, &[0]);
+        efivar(
+            &root,
+            "LoaderInfo",
+            LOADER_GUID,
+            &utf16("systemd-boot 257.1"),
+        );
+
+        let system = Probe::with_root(&root).probe();
+        assert_eq!(
+            system,
+            ProbedSystem {
+                uefi: true,
+                secure_boot: Some(false),
+                bootloader: Some("systemd-boot 257.1".to_string()),
+                root_filesystem: Some("xfs".to_string()),
+                kernel_name: Some("Linux".to_string()),
+                kernel_release: Some("6.12.4-1".to_string()),
+                architecture: Some("x86_64".to_string()),
+            }
+        );
+
+        let info = load_os_info(include_str!("../../../os/aerynos.json")).unwrap();
+        let report = system.check(&info);
+        let in_effect = report.in_effect().map(|c| c.feature).collect::<Vec<_>>();
+        assert_eq!(
+            in_effect,
+            vec![
+                "features.boot.firmware.uefi",
+                "features.boot.bootloader",
+                "features.filesystem.default",
+                "kernel.name",
+                "platform.architecture",
+            ]
+        );
+        assert_eq!(report.not_in_effect().count(), 0);
+    }
+
+    #[test]
+    fn test_probe_bios() {
+        let root = tree("bios");
+        fs::write(root.join(KERNEL_SYSCTL_DIR).join("arch"), "aarch64\n").unwrap();
+
+        let system = Probe::with_root(&root).probe();
+        assert!(!system.uefi);
+        assert_eq!(system.secure_boot, None);
+        assert_eq!(system.bootloader, None);
+
+        let mut info = load_os_info(include_str!("../../../os/aerynos.json")).unwrap();
+        info.system.features.boot.firmware.secure_boot = true;
+        let report = system.check(&info);
+
+        let uefi = report.get("features.boot.firmware.uefi").unwrap();
+        assert_eq!(uefi.status, FeatureStatus::NotInEffect);
+        assert_eq!(uefi.observed.as_deref(), Some("false"));
+        assert_eq!(
+            report
+                .get("features.boot.firmware.secure_boot")
+                .unwrap()
+                .status,
+            FeatureStatus::Unknown
+        );
+        assert_eq!(
+            report.get("features.boot.bootloader").unwrap().status,
+            FeatureStatus::Unknown
+        );
+        assert_eq!(
+            report.get("platform.architecture").unwrap().status,
+            FeatureStatus::NotInEffect
+        );
+        assert!(report.get("features.boot.firmware.bios").is_none());
+    }
+
+    #[test]
+    fn test_normalize_arch() {
+        assert_eq!(normalize_arch("amd64"), normalize_arch("x86_64"));
+        assert_eq!(normalize_arch("arm64"), "aarch64");
+        assert_eq!(normalize_arch("i686"), "x86");
+        assert_eq!(normalize_arch("armv7l"), "arm");
+        assert_eq!(normalize_arch("ppc64el"), "ppc64le");
+        assert_eq!(normalize_arch("unknown"), "unknown");
+    }
+
+    #[test]
+    fn test_systemd_arch() {
+        // Every systemd name maps to the kernel's, and back
+        for (systemd, kernel) in [
+            ("x86-64", "x86_64"),
+            ("x86", "x86"),
+            ("arm64", "aarch64"),
+            ("arm64-be", "aarch64_be"),
+            ("arm", "arm"),
+            ("arm-be", "armeb"),
+            ("alpha", "alpha"),
+            ("arc", "arc"),
+            ("arc-be", "arceb"),
+            ("cris", "cris"),
+            ("ia64", "ia64"),
+            ("loongarch64", "loongarch64"),
+            ("m68k", "m68k"),
+            ("mips", "mips"),
+            ("mips-le", "mipsel"),
+            ("mips64", "mips64"),
+            ("mips64-le", "mips64el"),
+            ("nios2", "nios2"),
+            ("parisc", "parisc"),
+            ("parisc64", "parisc64"),
+            ("ppc", "ppc"),
+            ("ppc-le", "ppcle"),
+            ("ppc64", "ppc64"),
+            ("ppc64-le", "ppc64le"),
+            ("riscv32", "riscv32"),
+            ("riscv64", "riscv64"),
+            ("s390", "s390"),
+            ("s390x", "s390x"),
+            ("sh", "sh"),
+            ("sh64", "sh64"),
+            ("sparc", "sparc"),
+            ("sparc64", "sparc64"),
+            ("tilegx", "tilegx"),
+        ] {
+            assert_eq!(normalize_arch(systemd), kernel, "{systemd}");
+            assert_eq!(systemd_arch(kernel), systemd, "{kernel}");
+            assert_eq!(systemd_arch(systemd), systemd, "{systemd}");
+        }
+
+        assert_eq!(systemd_arch("amd64"), "x86-64");
+        assert_eq!(systemd_arch("armv7l"), "arm");
+    }
+}
@@ -0,0 +1,415 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Conversion support for SBOM operating system components
+//!
+//! This module describes an [`OsInfo`] as a CycloneDX `operating-system`
+//! component and as an SPDX 2.3 package, so image SBOMs can carry the
+//! authoritative OS identity.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+
+use crate::{MaintainerRole, OsInfo, WebsiteScope};
+
+/// Prefix of os-info specific CycloneDX property names
+pub const PROPERTY_NAMESPACE: &str = "os-info";
+
+/// A CycloneDX component describing the operating system
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CycloneDxComponent {
+    /// Component type, always `operating-system`
+    #[serde(rename = "type")]
+    pub component_type: String,
+    /// Reference to the component within the BOM
+    #[serde(rename = "bom-ref")]
+    pub bom_ref: String,
+    /// Organisation that supplies the OS
+    pub supplier: CycloneDxSupplier,
+    /// OS identifier
+    pub name: String,
+    /// Full version
+    pub version: String,
+    /// Display name
+    pub description: String,
+    /// CPE 2.3 name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpe: Option<String>,
+    /// Package URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purl: Option<String>,
+    /// Links to project resources
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub external_references: Vec<CycloneDxReference>,
+    /// Additional name/value properties
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<CycloneDxProperty>,
+}
+
+/// A CycloneDX organizational entity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CycloneDxSupplier {
+    /// Organisation name
+    pub name: String,
+    /// Organisation websites
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub url: Vec<String>,
+    /// People to contact at the organisation
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub contact: Vec<CycloneDxContact>,
+}
+
+/// A CycloneDX organizational contact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CycloneDxContact {
+    /// Contact name
+    pub name: String,
+    /// Contact email
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+/// A CycloneDX external reference
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CycloneDxReference {
+    /// Reference type, e.g. `website` or `issue-tracker`
+    #[serde(rename = "type")]
+    pub reference_type: String,
+    /// Reference URL
+    pub url: String,
+}
+
+/// A CycloneDX name/value property
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CycloneDxProperty {
+    /// Property name
+    pub name: String,
+    /// Property value
+    pub value: String,
+}
+
+/// Convert an OSInfo into a CycloneDX component
+impl From<&OsInfo> for CycloneDxComponent {
+    fn from(info: &OsInfo) -> Self {
+        let identity = &info.metadata.identity;
+        let version = &info.metadata.version;
+
+        let mut external_references = references(info)
+            .into_iter()
+            .map(|(reference_type, url)| CycloneDxReference {
+                reference_type: reference_type.to_string(),
+                url,
+            })
+            .collect::<Vec<_>>();
+        if let Some(email) = info
+            .security_contact
+            .as_ref()
+            .and_then(|c| c.email.as_ref())
+        {
+            external_references.push(CycloneDxReference {
+                reference_type: "security-contact".to_string(),
+                url: format!("mailto:{email}"),
+            });
+        }
+
+        let property = |name: &str, value: &str| CycloneDxProperty {
+            name: format!("{PROPERTY_NAMESPACE}:{name}"),
+            value: value.to_string(),
+        };
+        let mut properties = vec![
+            property("build_id", &version.build_id),
+            property("version_id", &version.short),
+            property("released", &timestamp(&version.released)),
+        ];
+        if let Some(codename) = version.codename.as_ref() {
+            properties.push(property("codename", codename));
+        }
+        if let Some(id_like) = &identity.id_like {
+            properties.push(property("id_like", id_like));
+        }
+
+        Self {
+            component_type: "operating-system".to_string(),
+            bom_ref: format!("os:{}@{}", identity.id, version.full),
+            supplier: CycloneDxSupplier {
+                name: identity.name.clone(),
+                url: homepage(info).into_iter().collect(),
+                contact: suppliers(info)
+                    .into_iter()
+                    .map(|(name, email)| CycloneDxContact { name, email })
+                    .collect(),
+            },
+            name: identity.id.clone(),
+            version: version.full.clone(),
+            description: identity.display.clone(),
+            cpe: info.cpe().map(|cpe| cpe.to_string()),
+            purl: info.purl().map(|purl| purl.to_string()),
+            external_references,
+            properties,
+        }
+    }
+}
+
+/// An SPDX 2.3 package describing the operating system
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxPackage {
+    /// SPDX element identifier
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    /// OS identifier
+    pub name: String,
+    /// Full version
+    pub version_info: String,
+    /// Always `OPERATING-SYSTEM`
+    pub primary_package_purpose: String,
+    /// Supplier, as `Organization: name (email)`
+    pub supplier: String,
+    /// Where the OS can be downloaded, `NOASSERTION` when unknown
+    pub download_location: String,
+    /// Project homepage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    /// Files are not part of the package description
+    pub files_analyzed: bool,
+    /// Release date
+    pub release_date: String,
+    /// Display name
+    pub description: String,
+    /// Build details not covered by other fields
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Links to project resources
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub external_refs: Vec<SpdxExternalRef>,
+}
+
+/// An SPDX external reference
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxExternalRef {
+    /// One of `SECURITY`, `PACKAGE-MANAGER`, `PERSISTENT-ID` or `OTHER`
+    pub reference_category: String,
+    /// Reference type within the category
+    pub reference_type: String,
+    /// The reference itself
+    pub reference_locator: String,
+}
+
+/// Convert an OSInfo into an SPDX package
+impl From<&OsInfo> for SpdxPackage {
+    fn from(info: &OsInfo) -> Self {
+        let identity = &info.metadata.identity;
+        let version = &info.metadata.version;
+
+        let supplier = match suppliers(info).into_iter().find_map(|(_, email)| email) {
+            Some(email) => format!("Organization: {} ({email})", identity.name),
+            None => format!("Organization: {}", identity.name),
+        };
+
+        let mut external_refs = Vec::new();
+        if let Some(cpe) = info.cpe() {
+            external_refs.push(SpdxExternalRef {
+                reference_category: "SECURITY".to_string(),
+                reference_type: "cpe23Type".to_string(),
+                reference_locator: cpe.to_string(),
+            });
+        }
+        if let Some(purl) = info.purl() {
+            external_refs.push(SpdxExternalRef {
+                reference_category: "PACKAGE-MANAGER".to_string(),
+                reference_type: "purl".to_string(),
+                reference_locator: purl.to_string(),
+            });
+        }
+        external_refs.extend(references(info).into_iter().map(|(reference_type, url)| {
+            SpdxExternalRef {
+                reference_category: "OTHER".to_string(),
+                reference_type: reference_type.to_string(),
+                reference_locator: url,
+            }
+        }));
+
+        Self {
+            spdx_id: format!("SPDXRef-OperatingSystem-{}", spdx_id_safe(&identity.id)),
+            name: identity.id.clone(),
+            version_info: version.full.clone(),
+            primary_package_purpose: "OPERATING-SYSTEM".to_string(),
+            supplier,
+            download_location: "NOASSERTION".to_string(),
+            homepage: homepage(info),
+            files_analyzed: false,
+            release_date: timestamp(&version.released),
+            description: identity.display.clone(),
+            comment: Some(format!("Build ID: {}", version.build_id)),
+            external_refs,
+        }
+    }
+}
+
+/// Active founders, stewards and maintainers, with their email if they have one
+fn suppliers(info: &OsInfo) -> Vec<(String, Option<String>)> {
+    let mut groups = info.metadata.maintainers.iter().collect::<Vec<_>>();
+    groups.sort_by_key(|(group, _)| *group);
+
+    groups
+        .into_iter()
+        .flat_map(|(_, maintainers)| maintainers)
+        .filter(|m| m.end_date.is_none() && !matches!(m.role, MaintainerRole::Contributor))
+        .map(|m| {
+            let email = m.email.email().map(str::to_string);
+            (m.name.clone(), email)
+        })
+        .collect()
+}
+
+fn homepage(info: &OsInfo) -> Option<String> {
+    let mut homes = info
+        .resources
+        .websites
+        .values()
+        .filter(|site| site.scope == WebsiteScope::Home)
+        .map(|site| site.url.to_string())
+        .collect::<Vec<_>>();
+    homes.sort();
+    homes.into_iter().next()
+}
+
+/// Websites and social links as CycloneDX reference types, in a stable order
+fn references(info: &OsInfo) -> Vec<(&'static str, String)> {
+    let mut references = info
+        .resources
+        .websites
+        .values()
+        .filter_map(|site| {
+            let reference_type = match site.scope {
+                WebsiteScope::Home | WebsiteScope::Public => "website",
+                WebsiteScope::Documentation
+                | WebsiteScope::EndUserDocs
+                | WebsiteScope::DeveloperDocs => "documentation",
+                WebsiteScope::Support => "support",
+                WebsiteScope::BugTracker => "issue-tracker",
+                WebsiteScope::Developer => "vcs",
+                WebsiteScope::SecurityPolicy => "advisories",
+                WebsiteScope::PrivacyPolicy
+                | WebsiteScope::TermsOfService
+                | WebsiteScope::Legal => return None,
+            };
+            Some((reference_type, site.url.to_string()))
+        })
+        .chain(
+            info.resources
+                .social
+                .values()
+                .map(|link| ("social", link.url.to_string())),
+        )
+        .collect::<Vec<_>>();
+
+    if let Some(url) = &info.metadata.version.announcement {
+        references.push(("release-notes", url.to_string()));
+    }
+
+    references.sort();
+    references.dedup();
+    references
+}
+
+fn timestamp(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// SPDX identifiers may only contain letters, digits, `.` and `-`
+fn spdx_id_safe(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_os_info;
+    use serde_json::json;
+
+    fn sample() -> OsInfo {
+        load_os_info(include_str!("../../../os/aerynos.json")).unwrap()
+    }
+
+    #[test]
+    fn test_cyclonedx_component() {
+        let component = CycloneDxComponent::from(&sample());
+        let value = serde_json::to_value(&component).unwrap();
+
+        assert_eq!(value["type"], "operating-system");
+        assert_eq!(value["bom-ref"], "os:aerynos@0.2025.1");
+        assert_eq!(value["name"], "aerynos");
+        assert_eq!(value["version"], "0.2025.1");
+        assert_eq!(
+            value["cpe"],
+            "cpe:2.3:o:aerynos:aerynos:0.25.1:*:*:*:*:*:*:*"
+        );
+        assert_eq!(
+            value["supplier"],
+            json!({
+                "name": "AerynOS",
+                "url": ["https://aerynos.com"],
+                "contact": [
+                    { "name": "Core Team" },
+                    { "name": "Ikey Doherty", "email": "ikey@aerynos.com" }
+                ]
+            })
+        );
+        assert!(value["externalReferences"].as_array().unwrap().contains(
+            &json!({ "type": "issue-tracker", "url": "https://github.com/AerynOS/issues" })
+        ));
+        assert!(
+            value["properties"]
+                .as_array()
+                .unwrap()
+                .contains(&json!({ "name": "os-info:build_id", "value": "2025.1" }))
+        );
+    }
+
+    #[test]
+    fn test_spdx_package() {
+        let package = SpdxPackage::from(&sample());
+        let value = serde_json::to_value(&package).unwrap();
+
+        assert_eq!(value["SPDXID"], "SPDXRef-OperatingSystem-aerynos");
+        assert_eq!(value["primaryPackagePurpose"], "OPERATING-SYSTEM");
+        assert_eq!(
+            value["supplier"],
+            "Organization: AerynOS (ikey@aerynos.com)"
+        );
+        assert_eq!(value["homepage"], "https://aerynos.com");
+        assert_eq!(value["releaseDate"], "2025-03-18T00:00:00Z");
+        assert_eq!(value["filesAnalyzed"], false);
+        assert_eq!(value["comment"], "Build ID: 2025.1");
+        assert_eq!(
+            value["externalRefs"][0],
+            json!({
+                "referenceCategory": "SECURITY",
+                "referenceType": "cpe23Type",
+                "referenceLocator": "cpe:2.3:o:aerynos:aerynos:0.25.1:*:*:*:*:*:*:*"
+            })
+        );
+        assert!(value["externalRefs"].as_array().unwrap().contains(&json!({
+            "referenceCategory": "OTHER",
+            "referenceType": "vcs",
+            "referenceLocator": "https://github.com/AerynOS"
+        })));
+    }
+
+    #[test]
+    fn test_spdx_id_safe() {
+        assert_eq!(spdx_id_safe("my_os+1"), "my-os-1");
+    }
+}
@@ -2,19 +2,62 @@
 //
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::{OsInfo, TechnologyCapabilities};
+use crate::{Keyring, OsInfo, SignatureError, TechnologyCapabilities};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use snafu::{ResultExt as _, Snafu};
 
 #[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
 pub enum Error {
     #[snafu(display("failed to parse JSON"))]
     JsonParse { source: serde_json::Error },
 
+    #[snafu(display("failed to serialize JSON"))]
+    JsonSerialize { source: serde_json::Error },
+
     #[snafu(display("failed to read file"))]
     FsRead { source: std::io::Error },
+
+    #[snafu(display("failed to read {}", path.display()))]
+    ReadLayer {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("failed to parse {}", path.display()))]
+    ParseLayer {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[snafu(display("invalid document resolved from {}", paths(layers)))]
+    ResolveDocument {
+        layers: Vec<PathBuf>,
+        source: serde_json::Error,
+    },
+
+    #[snafu(display("no os-info document found below {}", root.display()))]
+    NoDocument { root: PathBuf },
+
+    #[snafu(display("failed to load trusted keys"))]
+    TrustedKeys { source: SignatureError },
+
+    #[snafu(display("failed to verify {}", path.display()))]
+    Verify {
+        path: PathBuf,
+        source: SignatureError,
+    },
+}
+
+/// Comma separated list of paths for error messages
+fn paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 /// Loads and parses an os-info.json string
@@ -38,3 +81,14 @@ pub fn load_technology_from_path<P: AsRef<Path>>(path: P) -> Result<TechnologyCa
     let content = fs::read_to_string(path).context(FsReadSnafu)?;
     load_technology(&content)
 }
+
+/// Loads a technology capabilities json file after checking its detached
+/// signature against `keyring`
+pub fn load_technology_verified<P: AsRef<Path>>(
+    path: P,
+    keyring: &Keyring,
+) -> Result<TechnologyCapabilities, Error> {
+    let path = path.as_ref();
+    keyring.verify_file(path).context(VerifySnafu { path })?;
+    load_technology_from_path(path)
+}
@@ -0,0 +1,465 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Conversion support for RFC 9116 `security.txt` files
+//!
+//! This module renders the [`SecurityContact`] and security policy websites of
+//! an [`OsInfo`] as a `/.well-known/security.txt` file, and parses such files
+//! back into structured form.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use snafu::{OptionExt as _, ResultExt as _, Snafu, ensure};
+
+use crate::{OsInfo, PgpKey, PgpKeyKind, SecurityContact, Url, WebsiteScope};
+
+/// Well-known location of the file on a website
+pub const SECURITY_TXT_PATH: &str = "/.well-known/security.txt";
+
+/// Errors raised while generating or parsing security.txt
+#[derive(Debug, Snafu)]
+pub enum SecurityTxtError {
+    #[snafu(display("no security contact email"))]
+    NoContact,
+
+    #[snafu(display("line {line}: expected `Field: value`"))]
+    Syntax { line: usize },
+
+    #[snafu(display("missing required `{field}` field"))]
+    MissingField { field: &'static str },
+
+    #[snafu(display("`Expires` must appear only once"))]
+    DuplicateExpires,
+
+    #[snafu(display("invalid `Expires` date"))]
+    Expires { source: chrono::ParseError },
+}
+
+/// Options for generating security.txt from os-info
+#[derive(Debug, Clone)]
+pub struct SecurityTxtOptions {
+    /// How long the generated file stays valid, RFC 9116 recommends less than a year
+    pub validity: Duration,
+    /// Reference time the validity is counted from
+    pub now: DateTime<Utc>,
+    /// Languages reports are accepted in
+    pub preferred_languages: Vec<String>,
+    /// Canonical URL the file is published at
+    pub canonical: Option<String>,
+}
+
+impl Default for SecurityTxtOptions {
+    fn default() -> Self {
+        Self {
+            validity: Duration::days(180),
+            now: Utc::now(),
+            preferred_languages: vec!["en".to_string()],
+            canonical: None,
+        }
+    }
+}
+
+/// Represents the fields of a security.txt file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityTxt {
+    /// Where to report vulnerabilities, as URIs
+    pub contact: Vec<String>,
+    /// When the file should be considered stale
+    pub expires: DateTime<Utc>,
+    /// Keys for encrypted communication, as URIs
+    pub encryption: Vec<String>,
+    /// Vulnerability disclosure policy links
+    pub policy: Vec<String>,
+    /// Languages reports are accepted in
+    pub preferred_languages: Vec<String>,
+    /// Canonical URLs of the file
+    pub canonical: Vec<String>,
+    /// Acknowledgment pages
+    pub acknowledgments: Vec<String>,
+    /// Security related job postings
+    pub hiring: Vec<String>,
+    /// Unrecognised fields in the order they appeared
+    pub extra_fields: Vec<(String, String)>,
+}
+
+impl SecurityTxt {
+    /// Creates a security.txt from the os-info security contact and websites
+    pub fn from_os_info(
+        info: &OsInfo,
+        options: &SecurityTxtOptions,
+    ) -> Result<Self, SecurityTxtError> {
+        let contact = info.security_contact.as_ref();
+
+        let policy = {
+            let mut urls = info
+                .resources
+                .websites
+                .values()
+                .filter(|site| site.scope == WebsiteScope::SecurityPolicy)
+                .map(|site| site.url.to_string())
+                .collect::<Vec<_>>();
+            urls.sort();
+            // The disclosure policy may be free text, only a link belongs here
+            if let Some(url) = contact
+                .and_then(|c| c.disclosure_policy.as_deref())
+                .and_then(|policy| policy.parse::<Url>().ok())
+                .filter(|url| !urls.contains(&url.to_string()))
+            {
+                urls.insert(0, url.to_string());
+            }
+            urls
+        };
+
+        // A policy page is not somewhere reports can be sent
+        let contacts = contact
+            .and_then(|c| c.email.as_ref())
+            .map(|email| vec![format!("mailto:{email}")])
+            .context(NoContactSnafu)?;
+
+        let encryption = contact
+            .and_then(|c| c.pgp_key.as_ref())
+            .and_then(encryption_uri)
+            .into_iter()
+            .collect();
+
+        Ok(Self {
+            contact: contacts,
+            expires: options.now + options.validity,
+            encryption,
+            policy,
+            preferred_languages: options.preferred_languages.clone(),
+            canonical: options.canonical.iter().cloned().collect(),
+            acknowledgments: vec![],
+            hiring: vec![],
+            extra_fields: vec![],
+        })
+    }
+
+    /// Returns true if the file has passed its expiry date
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires <= now
+    }
+}
+
+/// Only keys published at a URI, or identified by fingerprint, can be referenced
+fn encryption_uri(pgp_key: &PgpKey) -> Option<String> {
+    match pgp_key.kind() {
+        PgpKeyKind::Url(url) if url.starts_with("https://") => Some(url.clone()),
+        PgpKeyKind::Url(_) | PgpKeyKind::Wkd(_) | PgpKeyKind::Other(_) => None,
+        PgpKeyKind::Armored(_) | PgpKeyKind::Fingerprint(_) => pgp_key
+            .fingerprint()
+            .map(|fpr| format!("openpgp4fpr:{}", fpr.as_str().to_ascii_lowercase())),
+    }
+}
+
+impl Display for SecurityTxt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut field = |name: &str, values: &[String]| -> fmt::Result {
+            for value in values {
+                writeln!(f, "{name}: {value}")?;
+            }
+            Ok(())
+        };
+
+        field("Contact", &self.contact)?;
+        field(
+            "Expires",
+            &[self.expires.to_rfc3339_opts(SecondsFormat::Secs, true)],
+        )?;
+        field("Encryption", &self.encryption)?;
+        field("Policy", &self.policy)?;
+        field("Acknowledgments", &self.acknowledgments)?;
+        field("Hiring", &self.hiring)?;
+        field("Canonical", &self.canonical)?;
+        if !self.preferred_languages.is_empty() {
+            field(
+                "Preferred-Languages",
+                &[self.preferred_languages.join(", ")],
+            )?;
+        }
+        for (key, value) in &self.extra_fields {
+            writeln!(f, "{key}: {value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for SecurityTxt {
+    type Err = SecurityTxtError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut contact = vec![];
+        let mut expires = None;
+        let mut encryption = vec![];
+        let mut policy = vec![];
+        let mut preferred_languages = vec![];
+        let mut canonical = vec![];
+        let mut acknowledgments = vec![];
+        let mut hiring = vec![];
+        let mut extra_fields = vec![];
+
+        for (number, line) in signed_content(s) {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line.split_once(':').context(SyntaxSnafu { line: number })?;
+            let value = value.trim().to_string();
+
+            match key.trim().to_ascii_lowercase().as_str() {
+                "contact" => contact.push(value),
+                "expires" => {
+                    ensure!(expires.is_none(), DuplicateExpiresSnafu);
+                    expires = Some(
+                        DateTime::parse_from_rfc3339(&value)
+                            .context(ExpiresSnafu)?
+                            .with_timezone(&Utc),
+                    );
+                }
+                "encryption" => encryption.push(value),
+                "policy" => policy.push(value),
+                "preferred-languages" => preferred_languages.extend(
+                    value
+                        .split(',')
+                        .map(|lang| lang.trim().to_string())
+                        .filter(|lang| !lang.is_empty()),
+                ),
+                "canonical" => canonical.push(value),
+                "acknowledgments" => acknowledgments.push(value),
+                "hiring" => hiring.push(value),
+                _ => extra_fields.push((key.trim().to_string(), value)),
+            }
+        }
+
+        ensure!(!contact.is_empty(), MissingFieldSnafu { field: "Contact" });
+
+        Ok(Self {
+            contact,
+            expires: expires.context(MissingFieldSnafu { field: "Expires" })?,
+            encryption,
+            policy,
+            preferred_languages,
+            canonical,
+            acknowledgments,
+            hiring,
+            extra_fields,
+        })
+    }
+}
+
+/// Strip an OpenPGP cleartext signature framing, yielding numbered content lines
+///
+/// Dash-escaping only applies inside a signed body, elsewhere a leading `- `
+/// is content.
+fn signed_content(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    let signed = s
+        .trim_start()
+        .starts_with("-----BEGIN PGP SIGNED MESSAGE-----");
+    let mut in_headers = signed;
+
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .skip(usize::from(signed))
+        .take_while(|(_, line)| !line.starts_with("-----BEGIN PGP SIGNATURE-----"))
+        .filter(move |(_, line)| {
+            if in_headers {
+                in_headers = !line.trim().is_empty();
+                return false;
+            }
+            true
+        })
+        .map(move |(i, line)| match line.strip_prefix("- ") {
+            Some(unescaped) if signed => (i, unescaped),
+            _ => (i, line),
+        })
+}
+
+/// Recover a security contact from a parsed security.txt
+impl From<&SecurityTxt> for SecurityContact {
+    fn from(txt: &SecurityTxt) -> Self {
+        Self {
+            email: txt
+                .contact
+                .iter()
+                .find_map(|c| c.strip_prefix("mailto:")?.parse().ok()),
+            pgp_key: txt.encryption.iter().find_map(|uri| uri.parse().ok()),
+            disclosure_policy: txt.policy.first().cloned(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Extensions, Translations, Website, load_os_info};
+
+    fn options() -> SecurityTxtOptions {
+        SecurityTxtOptions {
+            validity: Duration::days(30),
+            now: DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            preferred_languages: vec!["en".to_string(), "de".to_string()],
+            canonical: Some("https://example.com/.well-known/security.txt".to_string()),
+        }
+    }
+
+    fn sample() -> OsInfo {
+        let mut info = load_os_info(include_str!("../../../sample.json")).unwrap();
+        info.security_contact = Some(SecurityContact {
+            email: Some("security@example.com".parse().unwrap()),
+            pgp_key: Some("https://example.com/security.asc".parse().unwrap()),
+            disclosure_policy: Some("90 days".to_string()),
+            ..Default::default()
+        });
+        info.resources.websites.insert(
+            "security".to_string(),
+            Website {
+                url: "https://example.com/security".parse().unwrap(),
+                display_name: "Security Policy".to_string(),
+                scope: WebsiteScope::SecurityPolicy,
+                translations: Translations::default(),
+                extensions: Extensions::new(),
+            },
+        );
+        info
+    }
+
+    #[test]
+    fn test_render() {
+        let txt = SecurityTxt::from_os_info(&sample(), &options()).unwrap();
+        let output = txt.to_string();
+
+        assert_eq!(
+            output,
+            "Contact: mailto:security@example.com\n\
+             Expires: 2025-01-31T00:00:00Z\n\
+             Encryption: https://example.com/security.asc\n\
+             Policy: https://example.com/security\n\
+             Canonical: https://example.com/.well-known/security.txt\n\
+             Preferred-Languages: en, de\n"
+        );
+        assert_eq!(output.parse::<SecurityTxt>().unwrap(), txt);
+    }
+
+    #[test]
+    fn test_disclosure_policy() {
+        let mut info = sample();
+        info.resources.websites.remove("security");
+
+        // Free text is not a policy link
+        let txt = SecurityTxt::from_os_info(&info, &options()).unwrap();
+        assert!(txt.policy.is_empty());
+
+        let contact = info.security_contact.as_mut().unwrap();
+        contact.disclosure_policy = Some("https://example.com/disclosure".to_string());
+        let txt = SecurityTxt::from_os_info(&info, &options()).unwrap();
+        assert_eq!(
+            txt.policy,
+            vec!["https://example.com/disclosure".to_string()]
+        );
+
+        // The policy is never promoted to a contact
+        info.security_contact.as_mut().unwrap().email = None;
+        assert!(matches!(
+            SecurityTxt::from_os_info(&info, &options()),
+            Err(SecurityTxtError::NoContact)
+        ));
+    }
+
+    #[test]
+    fn test_encryption_uri() {
+        let fingerprint = "AAAA BBBB CCCC DDDD EEEE FFFF 0000 1111 2222 3333"
+            .parse::<PgpKey>()
+            .unwrap();
+        assert_eq!(
+            encryption_uri(&fingerprint),
+            Some("openpgp4fpr:aaaabbbbccccddddeeeeffff0000111122223333".to_string())
+        );
+        assert_eq!(
+            encryption_uri(&"http://example.com/key.asc".parse().unwrap()),
+            None
+        );
+        assert_eq!(
+            encryption_uri(&"security@example.com".parse().unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_no_contact() {
+        let info = load_os_info(include_str!("../../../sample.json")).unwrap();
+        assert!(matches!(
+            SecurityTxt::from_os_info(&info, &options()),
+            Err(SecurityTxtError::NoContact)
+        ));
+    }
+
+    #[test]
+    fn test_parse_signed() {
+        let input = "-----BEGIN PGP SIGNED MESSAGE-----\n\
+                     Hash: SHA512\n\
+                     \n\
+                     # Our security address\n\
+                     contact: mailto:security@example.com\n\
+                     Contact: https://example.com/report\n\
+                     Expires: 2026-01-01T00:00:00.000Z\n\
+                     - Policy: https://example.com/policy\n\
+                     X-Custom: value\n\
+                     -----BEGIN PGP SIGNATURE-----\n\
+                     abc\n\
+                     -----END PGP SIGNATURE-----\n";
+        let txt = input.parse::<SecurityTxt>().unwrap();
+
+        assert_eq!(txt.contact.len(), 2);
+        assert_eq!(txt.policy, vec!["https://example.com/policy".to_string()]);
+        assert_eq!(
+            txt.extra_fields,
+            vec![("X-Custom".to_string(), "value".to_string())]
+        );
+
+        let contact = SecurityContact::from(&txt);
+        assert_eq!(contact.email.as_deref(), Some("security@example.com"));
+        assert_eq!(contact.pgp_key, None);
+    }
+
+    #[test]
+    fn test_parse_unsigned_dash() {
+        let input = "Contact: mailto:security@example.com\n\
+                     Expires: 2026-01-01T00:00:00Z\n\
+                     - Policy: https://example.com/policy\n";
+        let txt = input.parse::<SecurityTxt>().unwrap();
+
+        assert!(txt.policy.is_empty());
+        assert_eq!(
+            txt.extra_fields,
+            vec![(
+                "- Policy".to_string(),
+                "https://example.com/policy".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            "Expires: 2026-01-01T00:00:00Z".parse::<SecurityTxt>(),
+            Err(SecurityTxtError::MissingField { field: "Contact" })
+        ));
+        assert!(matches!(
+            "Contact: mailto:a@example.com\nExpires: tomorrow".parse::<SecurityTxt>(),
+            Err(SecurityTxtError::Expires { .. })
+        ));
+        assert!(matches!(
+            "Contact: mailto:a@example.com\nbroken line".parse::<SecurityTxt>(),
+            Err(SecurityTxtError::Syntax { line: 2 })
+        ));
+    }
+}
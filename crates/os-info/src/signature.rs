@@ -0,0 +1,545 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Detached ed25519 signatures for os-info and technology documents
+//!
+//! Signatures are computed over the compact [`Canonical`] form of the JSON as
+//! written in the file, so re-indenting or reordering a file doesn't
+//! invalidate them. Values are signed as they appear: a timestamp with a
+//! `+02:00` offset or an explicit `null` is not normalised first, so the
+//! signature always matches what the [`Loader`](crate::Loader) reads. Keys and
+//! signatures use minisign-style text encodings: an `untrusted comment:` line
+//! followed by a base64 line carrying the `Ed` algorithm tag, an 8 byte key
+//! id, and the key or signature bytes.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ed25519_dalek::{Signer as _, SigningKey, Verifier as _, VerifyingKey};
+use serde_json::Value;
+use sha2::{Digest as _, Sha256};
+use snafu::{OptionExt as _, ResultExt as _, Snafu, ensure};
+
+use crate::Canonical;
+
+/// Algorithm tag at the start of encoded keys and signatures
+const ALGORITHM: &[u8; 2] = b"Ed";
+/// Extension appended to a document path to locate its detached signature
+pub const SIGNATURE_EXTENSION: &str = "sig";
+/// Extension of public key files in a trusted key directory
+pub const PUBLIC_KEY_EXTENSION: &str = "pub";
+/// Trusted keys shipped by the vendor, relative to the root
+pub const VENDOR_KEYS_DIR: &str = "usr/lib/os-info/trusted-keys";
+/// Trusted keys added by the local administrator, relative to the root
+pub const LOCAL_KEYS_DIR: &str = "etc/os-info/trusted-keys";
+
+/// Errors raised while handling keys and signatures
+#[derive(Debug, Snafu)]
+pub enum SignatureError {
+    #[snafu(display("missing base64 payload line"))]
+    MissingPayload,
+
+    #[snafu(display("invalid base64 payload"))]
+    Base64 { source: base64::DecodeError },
+
+    #[snafu(display("unsupported algorithm, expected `Ed`"))]
+    Algorithm,
+
+    #[snafu(display("payload has length {actual}, expected {expected}"))]
+    Length { actual: usize, expected: usize },
+
+    #[snafu(display("invalid ed25519 public key"))]
+    PublicKey {
+        source: ed25519_dalek::SignatureError,
+    },
+
+    #[snafu(display("failed to read {}", path.display()))]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("failed to parse {}", path.display()))]
+    ParseFile {
+        path: PathBuf,
+        #[snafu(source(from(SignatureError, Box::new)))]
+        source: Box<SignatureError>,
+    },
+
+    #[snafu(display("missing signature {}", path.display()))]
+    MissingSignature { path: PathBuf },
+
+    #[snafu(display("failed to parse document {}", path.display()))]
+    Document {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[snafu(display("failed to canonicalise document"))]
+    Canonicalise {
+        #[snafu(source(from(crate::Error, Box::new)))]
+        source: Box<crate::Error>,
+    },
+
+    #[snafu(display("signed by untrusted key {key_id}"))]
+    UntrustedKey { key_id: KeyId },
+
+    #[snafu(display("signature does not match the document"))]
+    Mismatch {
+        source: ed25519_dalek::SignatureError,
+    },
+}
+
+/// Identifier of a signing key, derived from the public key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyId([u8; 8]);
+
+impl KeyId {
+    fn for_key(key: &VerifyingKey) -> Self {
+        let hash = Sha256::digest(key.as_bytes());
+        Self(hash[..8].try_into().expect("sha256 is 32 bytes"))
+    }
+}
+
+impl fmt::Display for KeyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016X}", u64::from_le_bytes(self.0))
+    }
+}
+
+/// A secret key used by vendors to sign documents
+pub struct SecretKey {
+    id: KeyId,
+    key: SigningKey,
+}
+
+impl SecretKey {
+    /// Creates a secret key from a 32 byte ed25519 seed
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let key = SigningKey::from_bytes(seed);
+        Self {
+            id: KeyId::for_key(&key.verifying_key()),
+            key,
+        }
+    }
+
+    /// Identifier of this key
+    pub fn id(&self) -> KeyId {
+        self.id
+    }
+
+    /// Returns the matching public key
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            id: self.id,
+            key: self.key.verifying_key(),
+        }
+    }
+
+    /// Signs the canonical form of a document layer, as parsed from its file
+    ///
+    /// To sign a typed [`OsInfo`](crate::OsInfo), write it out first and sign
+    /// the file with [`SecretKey::sign_file`].
+    pub fn sign_layer(&self, layer: &Value) -> Result<Signature, SignatureError> {
+        let message = layer.to_canonical_string().context(CanonicaliseSnafu)?;
+        Ok(Signature {
+            key_id: self.id,
+            signature: self.key.sign(message.as_bytes()),
+        })
+    }
+
+    /// Signs a document file, for writing to its detached `.sig` file
+    pub fn sign_file<P: AsRef<Path>>(&self, path: P) -> Result<Signature, SignatureError> {
+        self.sign_layer(&read_layer(path.as_ref())?)
+    }
+}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretKey").field("id", &self.id).finish()
+    }
+}
+
+/// A public key trusted to sign documents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    id: KeyId,
+    key: VerifyingKey,
+}
+
+impl PublicKey {
+    /// Identifier of this key
+    pub fn id(&self) -> KeyId {
+        self.id
+    }
+
+    /// Verifies a detached signature over the canonical form of a document layer
+    pub fn verify(&self, layer: &Value, signature: &Signature) -> Result<(), SignatureError> {
+        ensure!(
+            signature.key_id == self.id,
+            UntrustedKeySnafu {
+                key_id: signature.key_id
+            }
+        );
+        let message = layer.to_canonical_string().context(CanonicaliseSnafu)?;
+        self.key
+            .verify(message.as_bytes(), &signature.signature)
+            .context(MismatchSnafu)
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = SignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (id, bytes) = decode::<32>(s)?;
+        let key = VerifyingKey::from_bytes(&bytes).context(PublicKeySnafu)?;
+        Ok(Self { id, key })
+    }
+}
+
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encode(f, "os-info public key", self.id, self.key.as_bytes())
+    }
+}
+
+/// A detached signature over a canonical document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    key_id: KeyId,
+    signature: ed25519_dalek::Signature,
+}
+
+impl Signature {
+    /// Identifier of the key that made this signature
+    pub fn key_id(&self) -> KeyId {
+        self.key_id
+    }
+
+    /// Path of the detached signature for a document
+    pub fn path_for<P: AsRef<Path>>(document: P) -> PathBuf {
+        let mut path = document.as_ref().as_os_str().to_owned();
+        path.push(".");
+        path.push(SIGNATURE_EXTENSION);
+        path.into()
+    }
+
+    /// Reads the detached signature of a document
+    pub fn read_for<P: AsRef<Path>>(document: P) -> Result<Self, SignatureError> {
+        let path = Self::path_for(document);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return MissingSignatureSnafu { path }.fail();
+            }
+            Err(err) => return Err(err).context(ReadSnafu { path }),
+        };
+        content.parse().context(ParseFileSnafu { path })
+    }
+}
+
+impl FromStr for Signature {
+    type Err = SignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key_id, bytes) = decode::<64>(s)?;
+        Ok(Self {
+            key_id,
+            signature: ed25519_dalek::Signature::from_bytes(&bytes),
+        })
+    }
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encode(
+            f,
+            "os-info signature",
+            self.key_id,
+            &self.signature.to_bytes(),
+        )
+    }
+}
+
+/// A set of trusted public keys
+#[derive(Debug, Clone, Default)]
+pub struct Keyring {
+    keys: Vec<PublicKey>,
+}
+
+impl Keyring {
+    /// Creates an empty keyring
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the vendor trusted key directory below `root`
+    ///
+    /// Keys in [`LOCAL_KEYS_DIR`] are not included, as anyone able to write
+    /// them could also sign their own overrides. Use
+    /// [`Keyring::load_trusted_with_local`] to trust them explicitly.
+    pub fn load_trusted<P: AsRef<Path>>(root: P) -> Result<Self, SignatureError> {
+        let mut keyring = Self::new();
+        keyring.add_dir(root.as_ref().join(VENDOR_KEYS_DIR))?;
+        Ok(keyring)
+    }
+
+    /// Loads both the vendor and the local trusted key directories below `root`
+    pub fn load_trusted_with_local<P: AsRef<Path>>(root: P) -> Result<Self, SignatureError> {
+        let mut keyring = Self::load_trusted(&root)?;
+        keyring.add_dir(root.as_ref().join(LOCAL_KEYS_DIR))?;
+        Ok(keyring)
+    }
+
+    /// Adds a trusted key
+    pub fn add(&mut self, key: PublicKey) {
+        if !self.keys.contains(&key) {
+            self.keys.push(key);
+        }
+    }
+
+    /// Adds every `*.pub` key in a directory, ignoring a missing directory
+    pub fn add_dir<P: AsRef<Path>>(&mut self, dir: P) -> Result<(), SignatureError> {
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            return Ok(());
+        }
+
+        let mut paths = fs::read_dir(dir)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|e| e.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .context(ReadSnafu { path: dir })?;
+        paths.retain(|p| p.extension().is_some_and(|e| e == PUBLIC_KEY_EXTENSION));
+        paths.sort();
+
+        for path in paths {
+            let content = fs::read_to_string(&path).context(ReadSnafu { path: &path })?;
+            self.add(content.parse().context(ParseFileSnafu { path: &path })?);
+        }
+
+        Ok(())
+    }
+
+    /// Trusted keys in this keyring
+    pub fn keys(&self) -> &[PublicKey] {
+        &self.keys
+    }
+
+    /// Verifies a signature over a document layer using the trusted key it
+    /// was made with
+    pub fn verify(&self, layer: &Value, signature: &Signature) -> Result<KeyId, SignatureError> {
+        let key = self
+            .keys
+            .iter()
+            .find(|k| k.id == signature.key_id)
+            .context(UntrustedKeySnafu {
+                key_id: signature.key_id,
+            })?;
+        key.verify(layer, signature)?;
+        Ok(key.id)
+    }
+
+    /// Verifies a document file against its detached `.sig` file
+    pub fn verify_file<P: AsRef<Path>>(&self, path: P) -> Result<KeyId, SignatureError> {
+        let path = path.as_ref();
+        let layer = read_layer(path)?;
+        self.verify(&layer, &Signature::read_for(path)?)
+    }
+}
+
+/// Read and parse a document file without interpreting it
+fn read_layer(path: &Path) -> Result<Value, SignatureError> {
+    let content = fs::read_to_string(path).context(ReadSnafu { path })?;
+    serde_json::from_str(&content).context(DocumentSnafu { path })
+}
+
+fn encode(f: &mut fmt::Formatter<'_>, comment: &str, id: KeyId, bytes: &[u8]) -> fmt::Result {
+    let payload = [ALGORITHM.as_slice(), &id.0, bytes].concat();
+    writeln!(f, "untrusted comment: {comment} {id}")?;
+    writeln!(f, "{}", BASE64.encode(payload))
+}
+
+fn decode<const N: usize>(s: &str) -> Result<(KeyId, [u8; N]), SignatureError> {
+    let line = s
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with("untrusted comment:"))
+        .context(MissingPayloadSnafu)?;
+    let payload = BASE64.decode(line).context(Base64Snafu)?;
+
+    let expected = ALGORITHM.len() + 8 + N;
+    ensure!(
+        payload.len() == expected,
+        LengthSnafu {
+            actual: payload.len(),
+            expected
+        }
+    );
+    ensure!(payload.starts_with(ALGORITHM), AlgorithmSnafu);
+
+    let id = KeyId(payload[2..10].try_into().expect("length checked"));
+    let bytes = payload[10..].try_into().expect("length checked");
+    Ok((id, bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../../sample.json");
+
+    fn vendor_key() -> SecretKey {
+        SecretKey::from_seed(&[7; 32])
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = vendor_key();
+        let layer: Value = serde_json::from_str(SAMPLE).unwrap();
+        let signature = key.sign_layer(&layer).unwrap();
+
+        let mut keyring = Keyring::new();
+        keyring.add(key.public_key());
+        assert_eq!(keyring.verify(&layer, &signature).unwrap(), key.id());
+
+        // Formatting doesn't matter, content does
+        let compact: Value = serde_json::from_str(&layer.to_string()).unwrap();
+        assert!(keyring.verify(&compact, &signature).is_ok());
+
+        let mut tampered = layer.clone();
+        tampered["security_contact"]["email"] = "attacker@example.com".into();
+        assert!(matches!(
+            keyring.verify(&tampered, &signature),
+            Err(SignatureError::Mismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_untrusted_key() {
+        let layer: Value = serde_json::from_str(SAMPLE).unwrap();
+        let signature = SecretKey::from_seed(&[9; 32]).sign_layer(&layer).unwrap();
+
+        let mut keyring = Keyring::new();
+        keyring.add(vendor_key().public_key());
+        assert!(matches!(
+            keyring.verify(&layer, &signature),
+            Err(SignatureError::UntrustedKey { .. })
+        ));
+    }
+
+    #[test]
+    fn test_encoding_round_trip() {
+        let key = vendor_key();
+        let public = key.public_key();
+        let encoded = public.to_string();
+        assert!(encoded.starts_with("untrusted comment: os-info public key "));
+        assert_eq!(encoded.parse::<PublicKey>().unwrap(), public);
+
+        let signature = key.sign_layer(&serde_json::json!({"a": 1})).unwrap();
+        assert_eq!(
+            signature.to_string().parse::<Signature>().unwrap(),
+            signature
+        );
+
+        assert!(matches!(
+            "untrusted comment: x\nRWQ=".parse::<PublicKey>(),
+            Err(SignatureError::Length { .. })
+        ));
+        assert!(matches!(
+            "not base64!".parse::<Signature>(),
+            Err(SignatureError::Base64 { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sign_file() {
+        let dir =
+            std::env::temp_dir().join(format!("os-info-signature-file-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("os-info.json");
+        // Neither the offset nor the explicit null survive a typed round-trip
+        fs::write(
+            &path,
+            r#"{"metadata": {"version": {"released": "2025-03-18T02:00:00+02:00", "codename": null}}}"#,
+        )
+        .unwrap();
+
+        let key = vendor_key();
+        let mut keyring = Keyring::new();
+        keyring.add(key.public_key());
+        assert!(matches!(
+            keyring.verify_file(&path),
+            Err(SignatureError::MissingSignature { .. })
+        ));
+
+        fs::write(
+            Signature::path_for(&path),
+            key.sign_file(&path).unwrap().to_string(),
+        )
+        .unwrap();
+        assert_eq!(keyring.verify_file(&path).unwrap(), key.id());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_verified_technology() {
+        let dir = std::env::temp_dir().join(format!(
+            "os-info-signature-technology-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("moss.json");
+        fs::copy("../../technologies/moss.json", &path).unwrap();
+
+        let key = vendor_key();
+        let mut keyring = Keyring::new();
+        keyring.add(key.public_key());
+        assert!(matches!(
+            crate::load_technology_verified(&path, &keyring),
+            Err(crate::Error::Verify {
+                source: SignatureError::MissingSignature { .. },
+                ..
+            })
+        ));
+
+        fs::write(
+            Signature::path_for(&path),
+            key.sign_file(&path).unwrap().to_string(),
+        )
+        .unwrap();
+        let technology = crate::load_technology_verified(&path, &keyring).unwrap();
+        assert_eq!(technology.name, "moss");
+
+        // Tampering after signing is caught before the document is used
+        let content = fs::read_to_string(&path).unwrap();
+        fs::write(&path, content.replace("\"moss\"", "\"mass\"")).unwrap();
+        assert!(matches!(
+            crate::load_technology_verified(&path, &keyring),
+            Err(crate::Error::Verify {
+                source: SignatureError::Mismatch { .. },
+                ..
+            })
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_signature_path() {
+        assert_eq!(
+            Signature::path_for("/usr/lib/os-info.json"),
+            PathBuf::from("/usr/lib/os-info.json.sig")
+        );
+    }
+}
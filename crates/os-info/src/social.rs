@@ -0,0 +1,393 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Social platforms and handle extraction
+//!
+//! This module types the platform of a [`SocialLink`] and parses its URL into
+//! the handle or room a user would recognise, such as `@AerynOS` or
+//! `#aerynos:matrix.org`, plus a deep link for platforms that define one.
+
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::SocialLink;
+
+/// Platform a social link points to
+///
+/// Only the names documents are expected to use map to a variant, other
+/// spellings are kept as [`SocialPlatform::Other`] so they round-trip, and
+/// [`SocialPlatform::canonical`] resolves them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum SocialPlatform {
+    /// Matrix room or user
+    Matrix,
+    /// Mastodon or other fediverse account
+    Mastodon,
+    /// YouTube channel
+    Youtube,
+    /// Discord server invite
+    Discord,
+    /// IRC channel
+    Irc,
+    /// Bluesky profile
+    Bluesky,
+    /// Subreddit or Reddit user
+    Reddit,
+    /// Any other platform or spelling, by name as written
+    Other(String),
+}
+
+impl SocialPlatform {
+    /// Name used in os-info documents
+    pub fn as_str(&self) -> &str {
+        match self {
+            SocialPlatform::Matrix => "matrix",
+            SocialPlatform::Mastodon => "mastodon",
+            SocialPlatform::Youtube => "youtube",
+            SocialPlatform::Discord => "discord",
+            SocialPlatform::Irc => "irc",
+            SocialPlatform::Bluesky => "bluesky",
+            SocialPlatform::Reddit => "reddit",
+            SocialPlatform::Other(name) => name,
+        }
+    }
+
+    /// The known platform this is another spelling of, such as `fediverse`
+    pub fn canonical(&self) -> SocialPlatform {
+        match self {
+            SocialPlatform::Other(name) => match name.to_ascii_lowercase().as_str() {
+                "matrix" => SocialPlatform::Matrix,
+                "mastodon" | "fediverse" => SocialPlatform::Mastodon,
+                "youtube" => SocialPlatform::Youtube,
+                "discord" => SocialPlatform::Discord,
+                "irc" => SocialPlatform::Irc,
+                "bluesky" | "bsky" => SocialPlatform::Bluesky,
+                "reddit" => SocialPlatform::Reddit,
+                _ => self.clone(),
+            },
+            _ => self.clone(),
+        }
+    }
+
+    /// Extracts the handle, room or channel from a link on this platform
+    ///
+    /// Returns `None` for unknown platforms and URLs that don't match the
+    /// platform's layout.
+    pub fn handle(&self, url: &str) -> Option<String> {
+        let url = UrlParts::parse(url)?;
+
+        match self.canonical() {
+            SocialPlatform::Matrix => matrix_id(&url).map(|(_, id)| id),
+            SocialPlatform::Mastodon => {
+                let user = url.segment(0)?.strip_prefix('@')?;
+                let user = user.split('@').next().filter(|u| !u.is_empty())?;
+                Some(format!("@{user}@{}", url.host))
+            }
+            SocialPlatform::Youtube => match (url.segment(0)?, url.segment(1)) {
+                (handle, None) if handle.starts_with('@') => Some(handle.to_string()),
+                ("c" | "user" | "channel", Some(name)) => Some(name.to_string()),
+                _ => None,
+            },
+            SocialPlatform::Discord => match (url.host, url.segment(0)?, url.segment(1)) {
+                ("discord.gg", code, None) => Some(code.to_string()),
+                ("discord.com" | "discordapp.com", "invite", Some(code)) => Some(code.to_string()),
+                _ => None,
+            },
+            SocialPlatform::Irc => irc_channel(&url).map(|(_, channel)| channel),
+            SocialPlatform::Bluesky => match (url.scheme, url.segment(0)) {
+                ("at", _) => Some(format!("@{}", url.host)),
+                (_, Some("profile")) => url.segment(1).map(|actor| format!("@{actor}")),
+                _ => None,
+            },
+            SocialPlatform::Reddit => match (url.segment(0)?, url.segment(1)) {
+                ("r", Some(name)) => Some(format!("r/{name}")),
+                ("u" | "user", Some(name)) => Some(format!("u/{name}")),
+                _ => None,
+            },
+            SocialPlatform::Other(_) => None,
+        }
+    }
+
+    /// Whether `url` is a link on this platform
+    ///
+    /// Known platforms require a URL a handle can be extracted from, unknown
+    /// platforms accept any URL.
+    pub fn matches_url(&self, url: &str) -> bool {
+        matches!(self.canonical(), SocialPlatform::Other(_)) || self.handle(url).is_some()
+    }
+
+    /// Builds an application deep link for a link on this platform
+    ///
+    /// Matrix links become `matrix:` URIs, IRC links an `ircs://` (or `irc://`)
+    /// URL and Bluesky profiles an `at://` URI. Other platforms only have web
+    /// links, so `None` is returned.
+    pub fn deep_link(&self, url: &str) -> Option<String> {
+        let parsed = UrlParts::parse(url)?;
+
+        match self.canonical() {
+            SocialPlatform::Matrix => {
+                let (kind, id) = matrix_id(&parsed)?;
+                Some(format!("matrix:{kind}/{}", &id[1..]))
+            }
+            SocialPlatform::Irc => {
+                let (server, channel) = irc_channel(&parsed)?;
+                Some(format!("{server}/{}", channel.replace('#', "%23")))
+            }
+            SocialPlatform::Bluesky => {
+                let handle = self.handle(url)?;
+                Some(format!("at://{}", &handle[1..]))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for SocialPlatform {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "matrix" => SocialPlatform::Matrix,
+            "mastodon" => SocialPlatform::Mastodon,
+            "youtube" => SocialPlatform::Youtube,
+            "discord" => SocialPlatform::Discord,
+            "irc" => SocialPlatform::Irc,
+            "bluesky" => SocialPlatform::Bluesky,
+            "reddit" => SocialPlatform::Reddit,
+            _ => SocialPlatform::Other(name),
+        }
+    }
+}
+
+impl From<SocialPlatform> for String {
+    fn from(platform: SocialPlatform) -> Self {
+        platform.as_str().to_string()
+    }
+}
+
+impl Display for SocialPlatform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl SocialLink {
+    /// Handle, room or channel this link points to
+    pub fn handle(&self) -> Option<String> {
+        self.platform.handle(&self.url)
+    }
+
+    /// Application deep link, where the platform defines one
+    pub fn deep_link(&self) -> Option<String> {
+        self.platform.deep_link(&self.url)
+    }
+}
+
+/// The parts of a URL needed for handle extraction
+struct UrlParts<'a> {
+    scheme: &'a str,
+    host: &'a str,
+    port: Option<&'a str>,
+    path: Vec<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> UrlParts<'a> {
+    fn parse(url: &'a str) -> Option<Self> {
+        let (scheme, rest) = url.split_once("://")?;
+        let (rest, fragment) = match rest.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (rest, None),
+        };
+        let rest = rest.split('?').next().unwrap_or_default();
+        let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        };
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        if host.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            scheme,
+            host,
+            port,
+            path: path.split('/').filter(|s| !s.is_empty()).collect(),
+            fragment,
+        })
+    }
+
+    fn segment(&self, index: usize) -> Option<&'a str> {
+        self.path.get(index).copied()
+    }
+}
+
+/// Matrix id and its `matrix:` URI kind, from a matrix.to link
+fn matrix_id(url: &UrlParts<'_>) -> Option<(&'static str, String)> {
+    if url.host != "matrix.to" {
+        return None;
+    }
+    let id = url.fragment?.trim_start_matches('/');
+    let id = id.split('?').next().unwrap_or_default().replace("%23", "#");
+
+    let kind = match id.chars().next()? {
+        '#' => "r",
+        '!' => "roomid",
+        '@' => "u",
+        _ => return None,
+    };
+    id.contains(':').then_some((kind, id))
+}
+
+/// IRC server URL and channel, from an `irc://`/`ircs://` link or a Libera/OFTC web client
+fn irc_channel(url: &UrlParts<'_>) -> Option<(String, String)> {
+    let channel = match url.scheme {
+        "irc" | "ircs" => url
+            .fragment
+            .map(|f| format!("#{f}"))
+            .or_else(|| url.segment(0).map(|s| s.replace("%23", "#")))?,
+        _ => format!("#{}", url.fragment?.trim_start_matches('#')),
+    };
+    if channel.len() < 2 {
+        return None;
+    }
+
+    let server = match url.scheme {
+        "irc" | "ircs" => match url.port {
+            Some(port) => format!("{}://{}:{port}", url.scheme, url.host),
+            None => format!("{}://{}", url.scheme, url.host),
+        },
+        _ => format!("ircs://irc.{}", url.host.strip_prefix("web.")?),
+    };
+    Some((server, channel))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn handle(platform: SocialPlatform, url: &str) -> Option<String> {
+        platform.handle(url)
+    }
+
+    #[test]
+    fn test_handles() {
+        use SocialPlatform::*;
+
+        assert_eq!(
+            handle(Matrix, "https://matrix.to/#/#aerynos:matrix.org").as_deref(),
+            Some("#aerynos:matrix.org")
+        );
+        assert_eq!(
+            handle(Mastodon, "https://fosstodon.org/@aerynos").as_deref(),
+            Some("@aerynos@fosstodon.org")
+        );
+        assert_eq!(
+            handle(Youtube, "https://www.youtube.com/@AerynOS").as_deref(),
+            Some("@AerynOS")
+        );
+        assert_eq!(
+            handle(Youtube, "https://youtube.com/channel/UC123").as_deref(),
+            Some("UC123")
+        );
+        assert_eq!(
+            handle(Discord, "https://discord.gg/abc123").as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(
+            handle(Discord, "https://discord.com/invite/abc123").as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(
+            handle(Irc, "ircs://irc.libera.chat:6697/#aerynos").as_deref(),
+            Some("#aerynos")
+        );
+        assert_eq!(
+            handle(Bluesky, "https://bsky.app/profile/aerynos.com").as_deref(),
+            Some("@aerynos.com")
+        );
+        assert_eq!(
+            handle(Reddit, "https://www.reddit.com/r/AerynOS/").as_deref(),
+            Some("r/AerynOS")
+        );
+
+        assert_eq!(handle(Matrix, "https://example.com/#/#room:server"), None);
+        assert_eq!(handle(Reddit, "https://reddit.com/"), None);
+        assert_eq!(
+            handle(Other("forum".to_string()), "https://example.com"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_deep_links() {
+        use SocialPlatform::*;
+
+        assert_eq!(
+            Matrix
+                .deep_link("https://matrix.to/#/#aerynos:matrix.org")
+                .as_deref(),
+            Some("matrix:r/aerynos:matrix.org")
+        );
+        assert_eq!(
+            Matrix
+                .deep_link("https://matrix.to/#/@ikey:matrix.org")
+                .as_deref(),
+            Some("matrix:u/ikey:matrix.org")
+        );
+        assert_eq!(
+            Irc.deep_link("https://web.libera.chat/#aerynos").as_deref(),
+            Some("ircs://irc.libera.chat/%23aerynos")
+        );
+        assert_eq!(
+            Bluesky
+                .deep_link("https://bsky.app/profile/aerynos.com")
+                .as_deref(),
+            Some("at://aerynos.com")
+        );
+        assert_eq!(Youtube.deep_link("https://www.youtube.com/@AerynOS"), None);
+    }
+
+    #[test]
+    fn test_platform_names() {
+        assert_eq!(
+            SocialPlatform::from("bluesky".to_string()),
+            SocialPlatform::Bluesky
+        );
+        assert_eq!(
+            SocialPlatform::from("forum".to_string()),
+            SocialPlatform::Other("forum".to_string())
+        );
+        assert_eq!(String::from(SocialPlatform::Irc), "irc");
+
+        let fediverse = SocialPlatform::from("Fediverse".to_string());
+        assert_eq!(fediverse, SocialPlatform::Other("Fediverse".to_string()));
+        assert_eq!(fediverse.canonical(), SocialPlatform::Mastodon);
+        assert!(fediverse.matches_url("https://fosstodon.org/@AerynOS"));
+        assert_eq!(
+            SocialPlatform::from("bsky".to_string())
+                .deep_link("https://bsky.app/profile/aerynos.com")
+                .as_deref(),
+            Some("at://aerynos.com")
+        );
+    }
+
+    #[test]
+    fn test_platform_round_trip() {
+        for name in [
+            "mastodon",
+            "Mastodon",
+            "fediverse",
+            "bsky",
+            "BlueSky",
+            "forum",
+        ] {
+            let platform: SocialPlatform = serde_json::from_value(json!(name)).unwrap();
+            assert_eq!(serde_json::to_value(&platform).unwrap(), json!(name));
+        }
+    }
+}
@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::{Extensions, Translations, Url};
+
 /// Represents the capabilities of a technology, including version info and related links
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TechnologyCapabilities {
@@ -17,6 +19,15 @@ pub struct TechnologyCapabilities {
     pub description: String,
     /// Collection of related links, mapped by link ID
     pub links: HashMap<String, TechnologyLink>,
+    /// Translations of `description`
+    #[serde(flatten)]
+    pub translations: Translations,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(
+        flatten,
+        deserialize_with = "crate::locale::extensions_without_translations"
+    )]
+    pub extensions: Extensions,
 }
 
 /// Represents a link related to a technology capability
@@ -28,5 +39,8 @@ pub struct TechnologyLink {
     /// Category the link belongs to
     pub category: String,
     /// URL of the link
-    pub url: String,
+    pub url: Url,
+    /// Unknown and vendor extension fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
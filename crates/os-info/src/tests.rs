@@ -53,13 +53,14 @@ fn test_maintainer_role_serialization() {
     let maintainer = Maintainer {
         name: "Test User".to_string(),
         role: MaintainerRole::Founder,
-        email: "test@example.com".to_string(),
+        email: "test@example.com".parse().unwrap(),
         start_date: Some(
             chrono::DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z")
                 .unwrap()
                 .with_timezone(&Utc),
         ),
-        end_date: None,
+        end_date: Nullable::Absent,
+        extensions: Extensions::new(),
     };
 
     let serialized = serde_json::to_string(&maintainer).unwrap();
@@ -180,8 +181,8 @@ fn test_security_contact_and_policy_links() {
 
     // Check security contact
     assert_eq!(
-        os_info.security_contact.as_ref().unwrap().email,
-        Some("security@example.com".to_string())
+        os_info.security_contact.as_ref().unwrap().email.as_deref(),
+        Some("security@example.com")
     );
 
     // Check policy links in websites
@@ -232,9 +233,11 @@ fn test_security_contact_and_policy_links() {
 #[test]
 fn test_website_scope_serialization() {
     let website = Website {
-        url: "https://example.com".to_string(),
+        url: "https://example.com".parse().unwrap(),
         display_name: "Example".to_string(),
         scope: WebsiteScope::Public,
+        translations: Translations::default(),
+        extensions: Extensions::new(),
     };
 
     let serialized = serde_json::to_string(&website).unwrap();
@@ -376,3 +379,181 @@ fn test_date_parsing() {
             .with_timezone(&Utc)
     );
 }
+
+/// Tests that unknown and vendor extension fields survive a load/save cycle
+#[test]
+fn test_extension_round_trip() {
+    let mut original: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string("../../os/aerynos.json").unwrap()).unwrap();
+    original["x-aerynos-installer"] = serde_json::json!({ "default_desktop": "gnome" });
+    original["metadata"]["identity"]["x-aerynos-codename-art"] = "wings.svg".into();
+    original["system"]["features"]["measured_boot"] = true.into();
+
+    let mut os_info: OsInfo = serde_json::from_value(original.clone()).unwrap();
+    assert_eq!(
+        os_info.extensions["$schema"],
+        "https://raw.githubusercontent.com/AerynOS/os-info/refs/heads/main/schema/0.1/os-info.schema.json"
+    );
+    assert_eq!(
+        os_info.metadata.identity.extensions["x-aerynos-codename-art"],
+        "wings.svg"
+    );
+    assert_eq!(
+        os_info.system.features.extensions["measured_boot"],
+        serde_json::Value::Bool(true)
+    );
+
+    // Modifying a known field leaves the extensions intact
+    os_info.metadata.identity.display = "AerynOS 0.25.2".to_string();
+    original["metadata"]["identity"]["display"] = "AerynOS 0.25.2".into();
+
+    let saved = serde_json::to_value(&os_info).unwrap();
+    assert_eq!(saved, original);
+}
+
+/// Tests that an unknown key at every level of the document, and explicit
+/// nulls, survive a load/save cycle
+#[test]
+fn test_lossless_round_trip() {
+    /// Maps whose keys are user chosen rather than fields
+    const ENTRY_MAPS: &[&str] = &[
+        "/metadata/maintainers",
+        "/resources/websites",
+        "/resources/social",
+        "/resources/funding",
+    ];
+
+    fn add_unknown_keys(value: &mut serde_json::Value, pointer: &str) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    add_unknown_keys(child, &format!("{pointer}/{key}"));
+                }
+                if !ENTRY_MAPS.contains(&pointer) {
+                    map.insert("x-test-level".to_string(), pointer.into());
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    add_unknown_keys(item, &format!("{pointer}/{index}"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut original: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string("../../os/aerynos.json").unwrap()).unwrap();
+    original["metadata"]["version"]["codename"] = serde_json::Value::Null;
+    original["metadata"]["maintainers"]["founders"][0]["end_date"] = serde_json::Value::Null;
+    original["metadata"]["identity"]["x-test-cleared"] = serde_json::Value::Null;
+    add_unknown_keys(&mut original, "");
+
+    let os_info: OsInfo = serde_json::from_value(original.clone()).unwrap();
+    assert_eq!(
+        os_info.system.kernel.extensions["x-test-level"],
+        "/system/kernel"
+    );
+    assert_eq!(os_info.metadata.version.codename, Nullable::Null);
+
+    let saved = serde_json::to_value(&os_info).unwrap();
+    assert_eq!(saved, original);
+
+    // A value set after loading replaces the null
+    let mut os_info = os_info;
+    os_info.metadata.version.codename = Nullable::Value("wings".to_string());
+    let saved = serde_json::to_value(&os_info).unwrap();
+    assert_eq!(saved["metadata"]["version"]["codename"], "wings");
+
+    // Removed extension fields stay removed, even when they were null
+    os_info
+        .metadata
+        .identity
+        .extensions
+        .remove("x-test-cleared")
+        .unwrap();
+    let saved = serde_json::to_value(&os_info).unwrap();
+    assert!(
+        saved["metadata"]["identity"]
+            .get("x-test-cleared")
+            .is_none()
+    );
+
+    let mut technology: serde_json::Value =
+        serde_json::from_str(include_str!("../../../technologies/moss.json")).unwrap();
+    for link in technology["links"].as_object_mut().unwrap().values_mut() {
+        link["x-test-level"] = "link".into();
+    }
+    let parsed: TechnologyCapabilities = serde_json::from_value(technology.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), technology);
+}
+
+#[test]
+fn test_vendor_extension_keys() {
+    assert_eq!(
+        vendor_extension_key("aerynos", "installer"),
+        "x-aerynos-installer"
+    );
+    assert!(is_vendor_extension_key("x-aerynos-installer"));
+    assert!(is_vendor_extension_key("x-acme-boot-splash"));
+    assert!(!is_vendor_extension_key("x-aerynos"));
+    assert!(!is_vendor_extension_key("x--installer"));
+    assert!(!is_vendor_extension_key("x-AerynOS-installer"));
+    assert!(!is_vendor_extension_key("measured_boot"));
+}
+
+#[test]
+fn test_invalid_links_fail_to_load() {
+    let mut document: serde_json::Value =
+        serde_json::from_str(include_str!("../../../os/aerynos.json")).unwrap();
+    let os_info = load_os_info(&document.to_string()).unwrap();
+    assert!(matches!(
+        os_info.metadata.maintainers["core_team"][0].email,
+        Contact::Team(_)
+    ));
+
+    document["resources"]["websites"]["homepage"]["url"] = "aerynos.com".into();
+    let err = load_os_info(&document.to_string()).unwrap_err();
+    let source = std::error::Error::source(&err).unwrap();
+    assert!(
+        source
+            .to_string()
+            .contains("`aerynos.com` has no scheme, expected e.g. `https://aerynos.com`")
+    );
+
+    document["resources"]["websites"]["homepage"]["url"] = "https://aerynos.com".into();
+    document["metadata"]["maintainers"]["founders"][0]["email"] = "ikey".into();
+    let err = load_os_info(&document.to_string()).unwrap_err();
+    let source = std::error::Error::source(&err).unwrap();
+    assert!(
+        source
+            .to_string()
+            .contains("contact `ikey` must be an email address, `mailto:` URI or https URL")
+    );
+}
+
+/// Tests field lookup by JSON pointer and dotted path
+#[test]
+fn test_get() {
+    let os_info = load_os_info_from_path(SAMPLE_PATH).unwrap();
+
+    assert_eq!(
+        os_info.get("metadata.identity.name"),
+        Some(serde_json::json!("AerynOS"))
+    );
+    assert_eq!(
+        os_info.get("/metadata/version/short"),
+        os_info.get("metadata.version.short")
+    );
+    assert_eq!(
+        os_info.get("system.composition.technology.core.0"),
+        Some(serde_json::json!(
+            os_info.system.composition.technology.core[0]
+        ))
+    );
+    assert_eq!(os_info.get("metadata.identity.missing"), None);
+
+    assert_eq!(json_pointer(""), "");
+    assert_eq!(json_pointer("/a/b"), "/a/b");
+    assert_eq!(json_pointer("a.b~c/d"), "/a/b~0c~1d");
+}
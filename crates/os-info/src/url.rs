@@ -0,0 +1,424 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Validated URL, email and contact types
+//!
+//! Links, email addresses and maintainer contacts are checked when a document
+//! is loaded, so a malformed value is reported with the offending text rather
+//! than surfacing later in a generated file.
+
+use std::fmt::{self, Display};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use snafu::{Snafu, ensure};
+
+const MAILTO: &str = "mailto:";
+
+/// Errors raised while parsing a URL or contact
+#[derive(Debug, Snafu)]
+pub enum UrlError {
+    #[snafu(display("URL is empty"))]
+    Empty,
+
+    #[snafu(display("`{value}` contains whitespace"))]
+    Whitespace { value: String },
+
+    #[snafu(display("`{value}` has no scheme, expected e.g. `https://{value}`"))]
+    MissingScheme { value: String },
+
+    #[snafu(display("`{value}` has an invalid scheme `{scheme}`"))]
+    InvalidScheme { value: String, scheme: String },
+
+    #[snafu(display("`{value}` has no host"))]
+    MissingHost { value: String },
+
+    #[snafu(display("`{value}` is not a valid email address"))]
+    InvalidEmail { value: String },
+
+    #[snafu(display("contact `{value}` must be an email address, `mailto:` URI or https URL"))]
+    UnsupportedContact { value: String },
+}
+
+/// An absolute URL with a scheme and host, such as `https://aerynos.com`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Url(String);
+
+impl Url {
+    /// The URL as written
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Lowercase scheme, e.g. `https`
+    pub fn scheme(&self) -> &str {
+        self.0.split_once("://").map(|(s, _)| s).unwrap_or_default()
+    }
+
+    /// Host, without userinfo or port
+    pub fn host(&self) -> &str {
+        let rest = self.0.split_once("://").map(|(_, r)| r).unwrap_or_default();
+        let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+        let authority = authority.rsplit('@').next().unwrap_or_default();
+        match authority.rsplit_once(':') {
+            Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+            _ => authority,
+        }
+    }
+
+    /// Whether the URL uses https
+    pub fn is_https(&self) -> bool {
+        self.scheme() == "https"
+    }
+}
+
+impl FromStr for Url {
+    type Err = UrlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        ensure!(!value.is_empty(), EmptySnafu);
+        ensure!(
+            !value.contains(char::is_whitespace),
+            WhitespaceSnafu { value }
+        );
+
+        let Some((scheme, rest)) = value.split_once("://") else {
+            return MissingSchemeSnafu { value }.fail();
+        };
+        let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_lowercase())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "+-.".contains(c));
+        ensure!(valid_scheme, InvalidSchemeSnafu { value, scheme });
+        ensure!(
+            !rest.starts_with(['/', '?', '#', ':']) && !rest.is_empty(),
+            MissingHostSnafu { value }
+        );
+
+        Ok(Self(value.to_string()))
+    }
+}
+
+impl TryFrom<String> for Url {
+    type Error = UrlError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Url> for String {
+    fn from(url: Url) -> Self {
+        url.0
+    }
+}
+
+impl From<&Url> for String {
+    fn from(url: &Url) -> Self {
+        url.0.clone()
+    }
+}
+
+impl Deref for Url {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Url {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Url {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Url {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A plain email address, such as `security@aerynos.com`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Email(String);
+
+impl Email {
+    /// The address as written
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The address as a `mailto:` URI
+    pub fn uri(&self) -> String {
+        format!("{MAILTO}{}", self.0)
+    }
+}
+
+impl FromStr for Email {
+    type Err = UrlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_email(s).map(Self)
+    }
+}
+
+impl TryFrom<String> for Email {
+    type Error = UrlError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Email> for String {
+    fn from(email: Email) -> Self {
+        email.0
+    }
+}
+
+impl Deref for Email {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<&str> for Email {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl Display for Email {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// How to reach a maintainer
+///
+/// Documents may use a plain address, a `mailto:` URI, or an https link such
+/// as a GitHub team page.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Contact {
+    /// Email address, written without `mailto:`
+    Email(String),
+    /// Email address, written as a `mailto:` URI
+    Mailto(String),
+    /// Team page on a code forge, e.g. `https://github.com/orgs/AerynOS/teams/core`
+    Team(Url),
+    /// Any other https page
+    Url(Url),
+}
+
+impl Contact {
+    /// Email address, if this contact is one
+    pub fn email(&self) -> Option<&str> {
+        match self {
+            Contact::Email(address) | Contact::Mailto(address) => Some(address),
+            Contact::Team(_) | Contact::Url(_) => None,
+        }
+    }
+
+    /// Link to the contact, as a `mailto:` URI for email addresses
+    pub fn uri(&self) -> String {
+        match self {
+            Contact::Email(address) | Contact::Mailto(address) => format!("{MAILTO}{address}"),
+            Contact::Team(url) | Contact::Url(url) => url.to_string(),
+        }
+    }
+}
+
+impl FromStr for Contact {
+    type Err = UrlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        ensure!(!value.is_empty(), EmptySnafu);
+
+        if let Some(address) = value.strip_prefix(MAILTO) {
+            return parse_email(address).map(Contact::Mailto);
+        }
+
+        if value.contains("://") {
+            let url = value.parse::<Url>()?;
+            ensure!(url.is_https(), UnsupportedContactSnafu { value });
+            return Ok(if is_team_url(&url) {
+                Contact::Team(url)
+            } else {
+                Contact::Url(url)
+            });
+        }
+
+        ensure!(value.contains('@'), UnsupportedContactSnafu { value });
+        parse_email(value).map(Contact::Email)
+    }
+}
+
+impl TryFrom<String> for Contact {
+    type Error = UrlError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Contact> for String {
+    fn from(contact: Contact) -> Self {
+        contact.to_string()
+    }
+}
+
+impl Display for Contact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Contact::Email(address) => f.write_str(address),
+            Contact::Mailto(address) => write!(f, "{MAILTO}{address}"),
+            Contact::Team(url) | Contact::Url(url) => write!(f, "{url}"),
+        }
+    }
+}
+
+fn parse_email(value: &str) -> Result<String, UrlError> {
+    let valid = match value.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !value.contains(char::is_whitespace)
+                && !value.contains([':', ',', ';', '<', '>'])
+        }
+        None => false,
+    };
+    ensure!(valid, InvalidEmailSnafu { value });
+    Ok(value.to_string())
+}
+
+/// GitHub `/orgs/<org>/teams/<team>` and GitLab `/groups/<group>` pages
+fn is_team_url(url: &Url) -> bool {
+    let path = url
+        .split_once("://")
+        .and_then(|(_, rest)| rest.split_once('/'))
+        .map(|(_, path)| {
+            path.split('/')
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    match url.host() {
+        "github.com" => matches!(path.as_slice(), ["orgs", _, "teams", _]),
+        "gitlab.com" => matches!(path.as_slice(), ["groups", _, ..]),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url() {
+        let url = "https://user@aerynos.com:8443/path?q#frag"
+            .parse::<Url>()
+            .unwrap();
+        assert_eq!(url.scheme(), "https");
+        assert_eq!(url.host(), "aerynos.com");
+        assert!(url.is_https());
+
+        let irc = "ircs://irc.libera.chat:6697/#aerynos"
+            .parse::<Url>()
+            .unwrap();
+        assert_eq!(irc.host(), "irc.libera.chat");
+
+        let err = |s: &str| s.parse::<Url>().unwrap_err().to_string();
+        assert_eq!(
+            err("aerynos.com"),
+            "`aerynos.com` has no scheme, expected e.g. `https://aerynos.com`"
+        );
+        assert_eq!(err("https:///path"), "`https:///path` has no host");
+        assert_eq!(err("HTTP://x"), "`HTTP://x` has an invalid scheme `HTTP`");
+        assert_eq!(err("https://a b"), "`https://a b` contains whitespace");
+    }
+
+    #[test]
+    fn test_contact() {
+        assert_eq!(
+            "ikey@aerynos.com".parse::<Contact>().unwrap(),
+            Contact::Email("ikey@aerynos.com".to_string())
+        );
+        let mailto = "mailto:ikey@aerynos.com".parse::<Contact>().unwrap();
+        assert_eq!(mailto, Contact::Mailto("ikey@aerynos.com".to_string()));
+        assert_eq!(mailto.email(), Some("ikey@aerynos.com"));
+        assert_eq!(mailto.to_string(), "mailto:ikey@aerynos.com");
+        assert_eq!(
+            serde_json::to_string(&mailto).unwrap(),
+            "\"mailto:ikey@aerynos.com\""
+        );
+
+        let team = "https://github.com/orgs/AerynOS/teams/core"
+            .parse::<Contact>()
+            .unwrap();
+        assert!(matches!(team, Contact::Team(_)));
+        assert_eq!(team.email(), None);
+        assert_eq!(
+            team.to_string(),
+            "https://github.com/orgs/AerynOS/teams/core"
+        );
+
+        let page = "https://aerynos.com/team".parse::<Contact>().unwrap();
+        assert!(matches!(page, Contact::Url(_)));
+
+        let err = |s: &str| s.parse::<Contact>().unwrap_err().to_string();
+        assert_eq!(
+            err("mailto:nobody"),
+            "`nobody` is not a valid email address"
+        );
+        assert_eq!(
+            err("http://example.com"),
+            "contact `http://example.com` must be an email address, `mailto:` URI or https URL"
+        );
+        assert_eq!(
+            err("someone"),
+            "contact `someone` must be an email address, `mailto:` URI or https URL"
+        );
+    }
+
+    #[test]
+    fn test_email() {
+        let email = "security@aerynos.com".parse::<Email>().unwrap();
+        assert_eq!(email.as_str(), "security@aerynos.com");
+        assert_eq!(email.uri(), "mailto:security@aerynos.com");
+
+        let err = |s: &str| s.parse::<Email>().unwrap_err().to_string();
+        assert_eq!(err(""), "`` is not a valid email address");
+        assert_eq!(
+            err("mailto:security@aerynos.com"),
+            "`mailto:security@aerynos.com` is not a valid email address"
+        );
+        assert_eq!(err("security"), "`security` is not a valid email address");
+        assert!(serde_json::from_str::<Email>("\"not an address\"").is_err());
+    }
+}
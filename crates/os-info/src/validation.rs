@@ -0,0 +1,137 @@
+// SPDX-FileCopyrightText: Copyright © 2025 AerynOS Developers
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Checks that go beyond parsing a document
+//!
+//! Loading a document already checks required fields, dates, URLs and email
+//! addresses. [`OsInfo::validate`] adds the checks that relate several
+//! fields: links must belong to their declared platform and contacts must be
+//! usable.
+
+use snafu::{ResultExt, Snafu, ensure};
+
+use crate::{FundingError, OsInfo, SocialPlatform};
+
+/// Errors raised while validating a document
+#[derive(Debug, Snafu)]
+pub enum ValidationError {
+    #[snafu(display("invalid funding link"))]
+    Funding { source: FundingError },
+
+    #[snafu(display("social link `{key}` is not a {platform} URL: {url}"))]
+    SocialPlatformMismatch {
+        key: String,
+        platform: SocialPlatform,
+        url: String,
+    },
+
+    #[snafu(display("security contact has no email address"))]
+    NoSecurityEmail,
+
+    #[snafu(display("maintainer `{name}` in `{group}` left before joining"))]
+    MaintainerDates { group: String, name: String },
+}
+
+impl OsInfo {
+    /// Checks funding and social links against their platforms, and that the
+    /// security contact and maintainers can be reached
+    ///
+    /// Fields are checked in key order, and the first failure is returned.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut funding = self.resources.funding.iter().collect::<Vec<_>>();
+        funding.sort_by_key(|(key, _)| *key);
+        for (key, link) in funding {
+            link.validate(key).context(FundingSnafu)?;
+        }
+
+        let mut social = self.resources.social.iter().collect::<Vec<_>>();
+        social.sort_by_key(|(key, _)| *key);
+        for (key, link) in social {
+            ensure!(
+                link.platform.matches_url(&link.url),
+                SocialPlatformMismatchSnafu {
+                    key,
+                    platform: link.platform.clone(),
+                    url: &*link.url,
+                }
+            );
+        }
+
+        if let Some(contact) = &self.security_contact {
+            ensure!(contact.email.is_some(), NoSecurityEmailSnafu);
+        }
+
+        let mut groups = self.metadata.maintainers.iter().collect::<Vec<_>>();
+        groups.sort_by_key(|(group, _)| *group);
+        for (group, maintainers) in groups {
+            for maintainer in maintainers {
+                if let (Some(start), Some(end)) =
+                    (maintainer.start_date, maintainer.end_date.as_ref())
+                {
+                    ensure!(
+                        *end >= start,
+                        MaintainerDatesSnafu {
+                            group,
+                            name: &maintainer.name,
+                        }
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Nullable, SecurityContact, error_chain, load_os_info};
+
+    #[test]
+    fn test_validate() {
+        let load = || load_os_info(include_str!("../../../os/aerynos.json")).unwrap();
+        load().validate().unwrap();
+
+        let mut info = load();
+        let link = info.resources.funding.get_mut("kofi").unwrap();
+        link.url = "https://patreon.com/example".parse().unwrap();
+        assert_eq!(
+            error_chain(&info.validate().unwrap_err()),
+            "invalid funding link: funding link `kofi` is not a kofi URL: https://patreon.com/example"
+        );
+
+        let mut info = load();
+        let (key, link) = info.resources.social.iter_mut().next().unwrap();
+        let key = key.clone();
+        link.platform = SocialPlatform::Reddit;
+        link.url = "https://example.com/".parse().unwrap();
+        assert_eq!(
+            info.validate().unwrap_err().to_string(),
+            format!("social link `{key}` is not a reddit URL: https://example.com/")
+        );
+
+        let mut info = load();
+        info.security_contact = Some(SecurityContact::default());
+        assert!(matches!(
+            info.validate(),
+            Err(ValidationError::NoSecurityEmail)
+        ));
+
+        let mut info = load();
+        let maintainer = info
+            .metadata
+            .maintainers
+            .values_mut()
+            .flatten()
+            .next()
+            .unwrap();
+        maintainer.start_date = Some("2025-01-01T00:00:00Z".parse().unwrap());
+        maintainer.end_date = Nullable::Value("2024-01-01T00:00:00Z".parse().unwrap());
+        assert!(matches!(
+            info.validate(),
+            Err(ValidationError::MaintainerDates { .. })
+        ));
+    }
+}